@@ -2,8 +2,14 @@ use std::{env::current_dir, fs::create_dir_all};
 
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 use terra_bridge::msg::{
-    AdminsResponse, CollectionMappingResponse, ExecuteMsg, HistoryResponse, InstantiateMsg,
-    MigrateMsg, OperatorsResponse, QueryMsg,
+    AdminsResponse, AllowlistResponse, BalancesResponse, BondsResponse, CheckpointResponse,
+    ClaimsResponse, CollectionMappingResponse, DenylistResponse, EscrowedTokensResponse,
+    ExecuteMsg, FailedReleasesResponse, FrozenTokensResponse, GuardiansResponse,
+    HeartbeatsResponse, HistoryChainResponse, HistoryLinksResponse, HistoryResponse,
+    InstantiateMsg, OperatorsResponse, PauseStatusResponse, PendingBridgesResponse, QueryMsg,
+    QueuedReleasesResponse, RateLimitUsageResponse, ReconcileEscrowResponse, ReleaseNftsResponse,
+    RescuesResponse, TokenMetadataResponse, WatchersResponse, WindDownStatusResponse,
+    WithdrawalsResponse,
 };
 
 fn main() {
@@ -25,10 +31,106 @@ fn main() {
         &out_dir,
         "OperatorsResponse",
     );
+    export_schema_with_title(&schema_for!(DenylistResponse), &out_dir, "DenylistResponse");
     export_schema_with_title(
         &schema_for!(CollectionMappingResponse),
         &out_dir,
         "CollectionMappingResponse",
     );
+    export_schema_with_title(
+        &schema_for!(AllowlistResponse),
+        &out_dir,
+        "AllowlistResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(GuardiansResponse),
+        &out_dir,
+        "GuardiansResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(CheckpointResponse),
+        &out_dir,
+        "CheckpointResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HeartbeatsResponse),
+        &out_dir,
+        "HeartbeatsResponse",
+    );
+    export_schema_with_title(&schema_for!(BondsResponse), &out_dir, "BondsResponse");
+    export_schema_with_title(&schema_for!(WatchersResponse), &out_dir, "WatchersResponse");
+    export_schema_with_title(
+        &schema_for!(QueuedReleasesResponse),
+        &out_dir,
+        "QueuedReleasesResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(PauseStatusResponse),
+        &out_dir,
+        "PauseStatusResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(RateLimitUsageResponse),
+        &out_dir,
+        "RateLimitUsageResponse",
+    );
     export_schema_with_title(&schema_for!(HistoryResponse), &out_dir, "HistoryResponse");
+    export_schema_with_title(
+        &schema_for!(HistoryChainResponse),
+        &out_dir,
+        "HistoryChainResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HistoryLinksResponse),
+        &out_dir,
+        "HistoryLinksResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(EscrowedTokensResponse),
+        &out_dir,
+        "EscrowedTokensResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(PendingBridgesResponse),
+        &out_dir,
+        "PendingBridgesResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(FrozenTokensResponse),
+        &out_dir,
+        "FrozenTokensResponse",
+    );
+    export_schema_with_title(&schema_for!(RescuesResponse), &out_dir, "RescuesResponse");
+    export_schema_with_title(&schema_for!(BalancesResponse), &out_dir, "BalancesResponse");
+    export_schema_with_title(
+        &schema_for!(WithdrawalsResponse),
+        &out_dir,
+        "WithdrawalsResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(WindDownStatusResponse),
+        &out_dir,
+        "WindDownStatusResponse",
+    );
+    export_schema_with_title(&schema_for!(ClaimsResponse), &out_dir, "ClaimsResponse");
+    export_schema_with_title(
+        &schema_for!(FailedReleasesResponse),
+        &out_dir,
+        "FailedReleasesResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(ReconcileEscrowResponse),
+        &out_dir,
+        "ReconcileEscrowResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(ReleaseNftsResponse),
+        &out_dir,
+        "ReleaseNftsResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(TokenMetadataResponse),
+        &out_dir,
+        "TokenMetadataResponse",
+    );
 }
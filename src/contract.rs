@@ -1,15 +1,34 @@
 use cosmwasm_std::{
-    entry_point, Binary, CanonicalAddr, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, Binary, CanonicalAddr, ContractResult, Deps, DepsMut, Env, MessageInfo, Reply,
+    Response, StdResult,
 };
 
 use crate::{
     error::ContractError,
     execute::{
-        try_receive_nft, try_release_nft, try_update_collection_mappings, try_update_pause,
-        try_update_super_users,
+        try_acknowledge_bridge, try_approve_slash, try_backfill_escrow, try_bond,
+        try_cancel_queued_release, try_challenge_release, try_checkpoint, try_claim_bond,
+        try_claim_nft, try_dismiss_challenge, try_execute_queued_release, try_freeze_token,
+        try_heartbeat, try_hold_reclaim, try_mark_claimable, try_propose_slash, try_receive_nft,
+        try_reclaim_nft, try_record_failed_release, try_record_release, try_release_nft,
+        try_release_nfts, try_rescue_nft, try_resolve_dispute, try_retry_release, try_revoke_claim,
+        try_unbond, try_update_allowlist, try_update_bond_config, try_update_challenge_period,
+        try_update_circuit_breaker, try_update_collection_mappings, try_update_denylist,
+        try_update_guardians, try_update_liveness_window, try_update_pause, try_update_rate_limits,
+        try_update_reclaim_timeout, try_update_release_delay, try_update_super_users,
+        try_update_watchers, try_wind_down, try_withdraw_cw20, try_withdraw_native,
+        try_withdraw_nft,
     },
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    query::{query_admins, query_collection_mappings, query_history, query_operators},
+    query::{
+        query_admins, query_allowlist, query_balances, query_bonds, query_checkpoint, query_claims,
+        query_collection_mappings, query_denylist, query_escrowed_tokens,
+        query_escrowed_tokens_by_depositor, query_failed_releases, query_frozen_tokens,
+        query_guardians, query_heartbeats, query_history, query_history_chain, query_history_links,
+        query_operators, query_pause_status, query_pending_bridges, query_queued_releases,
+        query_rate_limit_usage, query_reconcile_escrow, query_rescues, query_token_metadata,
+        query_watchers, query_wind_down_status, query_withdrawals,
+    },
     state::{ADMINS, IS_PAUSED, OPERS},
 };
 
@@ -75,21 +94,116 @@ pub fn execute(
             try_update_super_users(deps, info, false, add, remove)
         }
 
-        // Sender must be admin or operator
+        ExecuteMsg::UpdateGuardians { add, remove } => {
+            try_update_guardians(deps, info, add, remove)
+        }
+
+        ExecuteMsg::UpdateWatchers { add, remove } => try_update_watchers(deps, info, add, remove),
+
+        ExecuteMsg::UpdateDenylist {
+            add,
+            remove,
+            contract,
+        } => try_update_denylist(deps, info, add, remove, contract),
+
+        ExecuteMsg::UpdateRateLimits { limits } => try_update_rate_limits(deps, info, limits),
+
+        ExecuteMsg::UpdateReleaseDelay { collection, delay } => {
+            try_update_release_delay(deps, info, collection, delay)
+        }
+
+        ExecuteMsg::UpdateChallengePeriod { period } => {
+            try_update_challenge_period(deps, info, period)
+        }
+
+        ExecuteMsg::DismissChallenge { queue_id } => try_dismiss_challenge(deps, info, queue_id),
+        ExecuteMsg::UpdateBondConfig { config } => try_update_bond_config(deps, info, config),
+
+        ExecuteMsg::ProposeSlash {
+            operator,
+            amount,
+            reason,
+        } => try_propose_slash(deps, info, operator, amount, reason),
+
+        ExecuteMsg::ApproveSlash { operator } => try_approve_slash(deps, info, operator),
+
+        ExecuteMsg::UpdateLivenessWindow { window } => {
+            try_update_liveness_window(deps, info, window)
+        }
+
+        ExecuteMsg::UpdateCircuitBreaker { circuit_breaker } => {
+            try_update_circuit_breaker(deps, info, circuit_breaker)
+        }
+
         ExecuteMsg::UpdatePause { pause, collection } => {
             try_update_pause(deps, info, pause, collection)
         }
 
+        ExecuteMsg::UpdateReclaimTimeout { timeout } => {
+            try_update_reclaim_timeout(deps, info, timeout)
+        }
+
+        ExecuteMsg::WindDown {} => try_wind_down(deps, env, info),
+
+        ExecuteMsg::BackfillEscrow {
+            collection,
+            start_after,
+            limit,
+        } => try_backfill_escrow(deps, env, info, collection, start_after, limit),
+
+        ExecuteMsg::RescueNft {
+            collection,
+            token_id,
+            recipient,
+        } => try_rescue_nft(deps, env, info, collection, token_id, recipient),
+
+        ExecuteMsg::WithdrawNative { recipient, amount } => {
+            try_withdraw_native(deps, env, info, recipient, amount)
+        }
+
+        ExecuteMsg::WithdrawCw20 {
+            token,
+            recipient,
+            amount,
+        } => try_withdraw_cw20(deps, env, info, token, recipient, amount),
+
+        ExecuteMsg::FreezeToken {
+            collection,
+            token_id,
+            note,
+        } => try_freeze_token(deps, env, info, collection, token_id, note),
+
+        ExecuteMsg::ResolveDispute {
+            collection,
+            token_id,
+            redirect_to,
+        } => try_resolve_dispute(deps, env, info, collection, token_id, redirect_to),
+
+        ExecuteMsg::RevokeClaim {
+            collection,
+            token_id,
+        } => try_revoke_claim(deps, info, collection, token_id),
+
+        // Sender must be admin or operator
         ExecuteMsg::UpdateCollectionMapping { add, remove } => {
             try_update_collection_mappings(deps, info, remove, add)
         }
 
+        ExecuteMsg::UpdateAllowlist {
+            collection,
+            add,
+            remove,
+            allowlist_only,
+        } => try_update_allowlist(deps, info, collection, add, remove, allowlist_only),
+
         ExecuteMsg::ReleaseNft {
             recipient,
             sn_collection,
             sn_address,
             token_id,
             recipient_is_contract,
+            msg,
+            checkpoint,
         } => try_release_nft(
             deps,
             env,
@@ -99,8 +213,94 @@ pub fn execute(
             recipient,
             token_id,
             recipient_is_contract,
+            msg,
+            checkpoint,
+        ),
+
+        ExecuteMsg::ReleaseNfts {
+            releases,
+            skip_invalid,
+            checkpoint,
+        } => try_release_nfts(deps, env, info, releases, skip_invalid, checkpoint),
+
+        ExecuteMsg::MarkClaimable {
+            recipient,
+            sn_collection,
+            sn_address,
+            token_id,
+            expires_in,
+        } => try_mark_claimable(
+            deps,
+            env,
+            info,
+            sn_collection,
+            sn_address,
+            recipient,
+            token_id,
+            expires_in,
+        ),
+
+        ExecuteMsg::AcknowledgeBridge {
+            seq,
+            sn_tx_hash,
+            success,
+        } => try_acknowledge_bridge(deps, env, info, seq, sn_tx_hash, success),
+
+        ExecuteMsg::HoldReclaim { seq, until } => try_hold_reclaim(deps, info, seq, until),
+        ExecuteMsg::Checkpoint { checkpoint } => try_checkpoint(deps, info, checkpoint),
+        ExecuteMsg::Heartbeat { last_sn_height } => try_heartbeat(deps, env, info, last_sn_height),
+
+        ExecuteMsg::RetryRelease {
+            collection,
+            token_id,
+            recipient,
+            recipient_is_contract,
+            msg,
+        } => try_retry_release(
+            deps,
+            env,
+            info,
+            collection,
+            token_id,
+            recipient,
+            recipient_is_contract,
+            msg,
         ),
 
+        // Sender must be admin or guardian
+        ExecuteMsg::CancelQueuedRelease { queue_id } => {
+            try_cancel_queued_release(deps, info, queue_id)
+        }
+
+        // Sender must be admin or watcher
+        ExecuteMsg::ChallengeRelease { queue_id, reason } => {
+            try_challenge_release(deps, env, info, queue_id, reason)
+        }
+
+        // Sender must be operator, or have been one
+        ExecuteMsg::Bond {} => try_bond(deps, info),
+        ExecuteMsg::Unbond { amount } => try_unbond(deps, env, info, amount),
+        ExecuteMsg::ClaimBond {} => try_claim_bond(deps, env, info),
+
+        // Sender must be the token's depositor
+        ExecuteMsg::ReclaimNft { seq } => try_reclaim_nft(deps, env, info, seq),
+
+        ExecuteMsg::WithdrawNft {
+            collection,
+            token_id,
+        } => try_withdraw_nft(deps, env, info, collection, token_id),
+
+        // Sender must be the claim's recipient
+        ExecuteMsg::ClaimNft {
+            collection,
+            token_id,
+        } => try_claim_nft(deps, env, info, collection, token_id),
+
+        // Anyone can execute a release once its delay has passed
+        ExecuteMsg::ExecuteQueuedRelease { queue_id } => {
+            try_execute_queued_release(deps, env, queue_id)
+        }
+
         // Sender must be a cw721 contract
         ExecuteMsg::ReceiveNft(receive_msg) => try_receive_nft(
             deps,
@@ -114,19 +314,95 @@ pub fn execute(
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
         QueryMsg::Admins {} => query_admins(deps),
         QueryMsg::Operators {} => query_operators(deps),
+        QueryMsg::Guardians {} => query_guardians(deps),
+        QueryMsg::Watchers {} => query_watchers(deps),
+        QueryMsg::Denylist { start_after, limit } => query_denylist(deps, start_after, limit),
         QueryMsg::CollectionMappings { source_contracts } => {
             query_collection_mappings(deps, source_contracts)
         }
+        QueryMsg::Allowlist {
+            collection,
+            address,
+        } => query_allowlist(deps, collection, address),
+        QueryMsg::PauseStatus { collection } => query_pause_status(deps, collection),
+        QueryMsg::RateLimitUsage {
+            collection,
+            user,
+            operator,
+        } => query_rate_limit_usage(deps, env, collection, user, operator),
         QueryMsg::HistoryByToken {
             collection_address,
             token_id,
             start_after,
             limit,
         } => query_history(deps, collection_address, token_id, start_after, limit),
+        QueryMsg::EscrowedTokens {
+            collection,
+            start_after,
+            limit,
+        } => query_escrowed_tokens(deps, collection, start_after, limit),
+        QueryMsg::EscrowedTokensByDepositor {
+            address,
+            start_after,
+            limit,
+        } => query_escrowed_tokens_by_depositor(deps, address, start_after, limit),
+        QueryMsg::TokenMetadata {
+            collection,
+            token_id,
+        } => query_token_metadata(deps, collection, token_id),
+        QueryMsg::PendingBridges {
+            older_than,
+            start_after,
+            limit,
+        } => query_pending_bridges(deps, env, older_than, start_after, limit),
+        QueryMsg::FrozenTokens {
+            collection,
+            start_after,
+            limit,
+        } => query_frozen_tokens(deps, collection, start_after, limit),
+        QueryMsg::QueuedReleases { start_after, limit } => {
+            query_queued_releases(deps, start_after, limit)
+        }
+        QueryMsg::Rescues { start_after, limit } => query_rescues(deps, start_after, limit),
+        QueryMsg::Balances {} => query_balances(deps, env),
+        QueryMsg::Bonds { start_after, limit } => query_bonds(deps, start_after, limit),
+        QueryMsg::Withdrawals { start_after, limit } => query_withdrawals(deps, start_after, limit),
+        QueryMsg::HistoryChain {} => query_history_chain(deps),
+        QueryMsg::HistoryLinks { start_after, limit } => {
+            query_history_links(deps, start_after, limit)
+        }
+        QueryMsg::Checkpoint { operator } => query_checkpoint(deps, operator),
+        QueryMsg::Heartbeats { start_after, limit } => {
+            query_heartbeats(deps, env, start_after, limit)
+        }
+        QueryMsg::WindDownStatus {} => query_wind_down_status(deps),
+        QueryMsg::Claims {
+            address,
+            start_after,
+            limit,
+        } => query_claims(deps, address, start_after, limit),
+        QueryMsg::FailedReleases {
+            collection,
+            start_after,
+            limit,
+        } => query_failed_releases(deps, collection, start_after, limit),
+        QueryMsg::ReconcileEscrow {
+            collection,
+            start_after,
+            limit,
+        } => query_reconcile_escrow(deps, env, collection, start_after, limit),
+    }
+}
+
+#[entry_point]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.result {
+        ContractResult::Err(error) => try_record_failed_release(deps, env, msg.id, error),
+        ContractResult::Ok(_) => try_record_release(deps, msg.id),
     }
 }
 
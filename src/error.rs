@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,9 +18,135 @@ pub enum ContractError {
     #[error("Collection mapping already exists for Terra address {source_addr:?}")]
     MappingExists { source_addr: String },
 
+    #[error("Address {address:?} is not a cw721 collection")]
+    InvalidCollection { address: String },
+
     #[error("Collection mapping not found for Terra address {source_addr:?}")]
     MappingNotFound { source_addr: String },
 
     #[error("Bridge is in the paused state. Tokens cannot be transfered in or out.")]
     BridgePaused {},
+
+    #[error("No operator has sent a heartbeat recently. The bridge is not receiving tokens")]
+    RelayerOffline {},
+
+    #[error("The bridge has been wound down and no longer receives tokens")]
+    BridgeWoundDown {},
+
+    #[error("Tokens can only be withdrawn once the bridge has been wound down")]
+    BridgeNotWoundDown {},
+
+    #[error("Address {address:?} is not allowed to use the bridge")]
+    AddressDenied { address: String },
+
+    #[error("Address {address:?} is not allowlisted for this collection")]
+    NotAllowlisted { address: String },
+
+    #[error("Circuit breaker tripped for {scope}: {reason}")]
+    CircuitBreakerTripped { scope: String, reason: String },
+
+    #[error("Rate limit for {scope} {subject:?} exceeded")]
+    RateLimitExceeded { scope: String, subject: String },
+
+    #[error("Checkpoint can not move back from SN height {sn_height} event {event_index}")]
+    CheckpointRegression { sn_height: u64, event_index: u32 },
+
+    #[error("Payload of {size} bytes exceeds the maximum of {max} bytes")]
+    PayloadTooLarge { size: usize, max: usize },
+
+    #[error("A payload can only be forwarded to a contract recipient")]
+    PayloadRequiresContract {},
+
+    #[error("No failed release found for token {token_id:?}")]
+    FailedReleaseNotFound { token_id: String },
+
+    #[error("Token {token_id:?} is awaiting a claim")]
+    TokenClaimable { token_id: String },
+
+    #[error("Token {token_id:?} appears more than once in the batch")]
+    DuplicateRelease { token_id: String },
+
+    #[error("Bridge record {seq} is not awaiting acknowledgement")]
+    BridgeNotPending { seq: u64 },
+
+    #[error("Bridge record {seq} can not be reclaimed until {available_at}")]
+    ReclaimNotReady { seq: u64, available_at: u64 },
+
+    #[error("Reclaiming bridge record {seq} is blocked by an operator")]
+    ReclaimBlocked { seq: u64 },
+
+    #[error("Token {token_id:?} is in the escrow ledger and can not be rescued")]
+    TokenEscrowed { token_id: String },
+
+    #[error("Withdrawal would spend funds bonded by operators")]
+    BondedFunds {},
+
+    #[error("Operator bonding is not enabled")]
+    BondingDisabled {},
+
+    #[error("Bonding can not be disabled or change denom while funds are bonded")]
+    BondsOutstanding {},
+
+    #[error("Bonds must be paid in a single coin of {denom:?}")]
+    InvalidBond { denom: String },
+
+    #[error("The bond is locked while the address is an operator")]
+    BondLocked {},
+
+    #[error("Only {available} is bonded")]
+    InsufficientBond { available: Uint128 },
+
+    #[error("Nothing to claim until {available_at}")]
+    UnbondingNotReady { available_at: u64 },
+
+    #[error("No unbonded funds to claim")]
+    NothingToClaim {},
+
+    #[error("No slash has been proposed for operator {operator:?}")]
+    SlashNotProposed { operator: String },
+
+    #[error("The slash has already been approved by this admin")]
+    SlashAlreadyApproved {},
+
+    #[error("Withdrawal amount must be greater than zero")]
+    EmptyWithdrawal {},
+
+    #[error("Token {token_id:?} is frozen")]
+    TokenFrozen { token_id: String },
+
+    #[error("Token {token_id:?} is not frozen")]
+    TokenNotFrozen { token_id: String },
+
+    #[error("Token {token_id:?} already has a queued release")]
+    TokenQueued { token_id: String },
+
+    #[error("Releases from collection {collection:?} are time-locked and must be queued")]
+    ReleaseTimeLocked { collection: String },
+
+    #[error("Queued release {queue_id} has been challenged")]
+    ReleaseChallenged { queue_id: u64 },
+
+    #[error("Queued release {queue_id} has not been challenged")]
+    ReleaseNotChallenged { queue_id: u64 },
+
+    #[error("Queued release {queue_id} unlocked at {unlock_at} and can no longer be challenged")]
+    ChallengePeriodEnded { queue_id: u64, unlock_at: u64 },
+
+    #[error("No queued release found with id {queue_id}")]
+    QueuedReleaseNotFound { queue_id: u64 },
+
+    #[error("Queued release {queue_id} is for a deposit that is no longer escrowed")]
+    QueuedDepositChanged { queue_id: u64 },
+
+    #[error("Queued release {queue_id} is locked until {unlock_at}")]
+    ReleaseLocked { queue_id: u64, unlock_at: u64 },
+
+    #[error("No claim found for token {token_id:?}")]
+    ClaimNotFound { token_id: String },
+
+    #[error("The claim for token {token_id:?} has expired")]
+    ClaimExpired { token_id: String },
+
+    #[error("Token {token_id:?} is not held by the bridge")]
+    TokenNotInCustody { token_id: String },
 }
@@ -1,17 +1,39 @@
+use std::collections::BTreeSet;
+
 use cosmwasm_std::{
-    from_binary, to_binary, Binary, CanonicalAddr, DepsMut, Env, MessageInfo, Response, StdResult,
-    WasmMsg,
+    from_binary, to_binary, Addr, BankMsg, Binary, CanonicalAddr, Coin, Deps, DepsMut, Env, Event,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
+use cw20::Cw20ExecuteMsg;
 use cw721::Cw721ExecuteMsg::{SendNft, TransferNft};
+use cw_storage_plus::Bound;
 
 use crate::{
     error::ContractError,
-    msg::CollectionMapping,
+    msg::{BondConfigMsg, CollectionMapping, ReleaseNftsResponse, ReleaseRequest, SkippedRelease},
     state::{
-        save_history, BridgeRecord, ADMINS, IS_COLL_PAUSED, IS_PAUSED, OPERS, SN_TO_TERRA_MAP,
-        TERRA_TO_SN_MAP,
+        latest_history, latest_inbound_history, next_bridge_seq, remove_escrow,
+        remove_queued_release, save_escrow, save_history, save_pending_release,
+        save_queued_release, save_rescue, save_withdrawal, window_usage, write_history,
+        Acknowledgement, Bond, BondConfig, BridgeRecord, BridgeStatus, Challenge, Checkpoint,
+        CircuitBreaker, Claim, DeliveryMode, EscrowPK, EscrowRecord, FailedRelease, FrozenToken,
+        Heartbeat, MetadataSnapshot, PendingRelease, QueuedRelease, RateLimits, RateScope,
+        ReclaimHold, RescueRecord, SlashProposal, Trip, TripReason, WithdrawalRecord,
+        WithdrawnFunds, ACKNOWLEDGEMENTS, ADMINS, ALLOWLIST, ALLOWLIST_ONLY, BONDS, BOND_CONFIG,
+        BREAKER_USAGE, BRIDGE_TRIP, CHALLENGE_PERIOD, CHECKPOINT, CHECKPOINTS, CIRCUIT_BREAKER,
+        CLAIMS, CLAIMS_BY_RECIPIENT, COLLECTION_INFO, COLL_TRIPS, DEFAULT_LIMIT, DENYLIST,
+        DENYLIST_CONTRACT, ESCROW, FAILED_RELEASES, FROZEN_TOKENS, GUARDIANS, HEARTBEATS, HISTORY,
+        HISTORY_COUNT, IS_COLL_PAUSED, IS_PAUSED, LAST_HEARTBEAT, LIVENESS_WINDOW, MAX_LIMIT,
+        MAX_PAYLOAD_SIZE, METADATA, OPERS, PENDING_BRIDGES, PENDING_RELEASES, QUEUED_RELEASES,
+        QUEUED_TOKENS, RATE_LIMITS, RATE_USAGE, RECLAIM_HOLDS, RECLAIM_TIMEOUT, RELEASE_DELAYS,
+        SLASH_PROPOSALS, SN_TO_TERRA_MAP, TERRA_TO_SN_MAP, TOTAL_BONDED, WATCHERS, WOUND_DOWN_AT,
+    },
+    utils::{
+        check_is_admin, check_is_contract, check_is_denied, check_is_guardian, check_is_live,
+        check_is_operator, check_is_paused, check_is_watcher, history_chain_attributes,
+        normalize_denied_address, query_collection_info, query_nft_info, query_owner_of,
+        reclaimable_at, set_paused, sha_256,
     },
-    utils::{check_is_admin, check_is_operator, check_is_paused},
 };
 
 /// Allows operators to release NFTs from bridge escrow.
@@ -35,7 +57,7 @@ pub fn try_update_super_users(
 
     // Check if sender is an admin
     let admins: Vec<CanonicalAddr> = ADMINS.load(deps.storage)?;
-    let sender_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     if !admins.contains(&sender_raw) {
         return Err(ContractError::Unauthorized {});
     }
@@ -82,6 +104,82 @@ pub fn try_update_super_users(
     Ok(Response::default().add_attribute("action", action))
 }
 
+/// Allows admins to add and remove guardians, who can cancel queued releases.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `add_list` - the addresses to add
+/// * `remove_list` - the addresses to remove
+pub fn try_update_guardians(
+    deps: DepsMut,
+    info: MessageInfo,
+    add_list: Option<Vec<String>>,
+    remove_list: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let guardians = GUARDIANS.may_load(deps.storage)?.unwrap_or_default();
+    let guardians = update_address_list(&deps, guardians, add_list, remove_list)?;
+    GUARDIANS.save(deps.storage, &guardians)?;
+
+    Ok(Response::default().add_attribute("action", "update_guardians"))
+}
+
+/// Allows admins to add and remove watchers, who can challenge queued releases.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `add_list` - the addresses to add
+/// * `remove_list` - the addresses to remove
+pub fn try_update_watchers(
+    deps: DepsMut,
+    info: MessageInfo,
+    add_list: Option<Vec<String>>,
+    remove_list: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let watchers = WATCHERS.may_load(deps.storage)?.unwrap_or_default();
+    let watchers = update_address_list(&deps, watchers, add_list, remove_list)?;
+    WATCHERS.save(deps.storage, &watchers)?;
+
+    Ok(Response::default().add_attribute("action", "update_watchers"))
+}
+
+/// Adds the `add_list` addresses to `list`, then removes the `remove_list` addresses
+fn update_address_list(
+    deps: &DepsMut,
+    mut list: Vec<CanonicalAddr>,
+    add_list: Option<Vec<String>>,
+    remove_list: Option<Vec<String>>,
+) -> StdResult<Vec<CanonicalAddr>> {
+    for addr in add_list.unwrap_or_default() {
+        let addr_raw = deps.api.addr_canonicalize(&addr)?;
+        if !list.contains(&addr_raw) {
+            list.push(addr_raw);
+        }
+    }
+    let to_remove = remove_list
+        .unwrap_or_default()
+        .iter()
+        .map(|addr| deps.api.addr_canonicalize(addr)) // also validates each address
+        .collect::<StdResult<Vec<CanonicalAddr>>>()?;
+    list.retain(|addr| !to_remove.contains(addr));
+    Ok(list)
+}
+
 /// Fetches all admins
 ///
 /// # Arguments
@@ -102,13 +200,15 @@ pub fn try_update_pause(
         return Err(ContractError::Unauthorized {});
     }
 
-    // Update state with the new value
+    // Update state with the new value. Any earlier trip no longer explains the pause state
     if let Some(coll) = collection {
         // Verify the collection is valid
         let coll_valid = deps.api.addr_validate(&coll)?;
-        IS_COLL_PAUSED.save(deps.storage, coll_valid, &pause)?;
+        IS_COLL_PAUSED.save(deps.storage, coll_valid.to_owned(), &pause)?;
+        COLL_TRIPS.remove(deps.storage, coll_valid);
     } else {
         IS_PAUSED.save(deps.storage, &pause)?;
+        BRIDGE_TRIP.remove(deps.storage);
     }
 
     Ok(Response::new()
@@ -116,203 +216,2942 @@ pub fn try_update_pause(
         .add_attribute("new_value", pause.to_string()))
 }
 
-/// Updates the collection mappings in storage.
-/// All items in `rem_list` are removed before adding items from `add_list`.
-/// * Sender must be an admin or operator
+/// Updates the addresses that can not use the bridge. Both Terra and SN addresses can be denied.
+/// All items in `remove_list` are removed after adding items from `add_list`.
 ///
 /// # Arguments
 ///
-/// * `deps` - a mutable reference to Extern containing all the contract's external dependencies
-/// * `info` - additional information about the contract's caller
-/// * `add_list` - a list of [CollectionMapping]s to be stored
-/// * `rem_list` - a list of Terra contract addresses to be cleared from storage
-pub fn try_update_collection_mappings(
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `add_list` - the addresses to deny
+/// * `remove_list` - the addresses to allow again
+/// * `contract` - optional external denylist contract. Use an empty string to stop querying it
+pub fn try_update_denylist(
     deps: DepsMut,
     info: MessageInfo,
-    rem_list: Option<Vec<CollectionMapping>>,
-    add_list: Option<Vec<CollectionMapping>>,
+    add_list: Option<Vec<String>>,
+    remove_list: Option<Vec<String>>,
+    contract: Option<String>,
 ) -> Result<Response, ContractError> {
-    // Verify sender is an operator
+    // Verify sender is an admin
     let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
-    if !check_is_operator(deps.storage, sender_raw)? {
+    if !check_is_admin(deps.storage, sender_raw)? {
         return Err(ContractError::Unauthorized {});
     }
 
-    // Remove items first so we can safely update a key's mapping in one message
-    for pair in rem_list.unwrap_or_default() {
-        let source = deps.api.addr_validate(&pair.source)?;
-        let existing_dest = TERRA_TO_SN_MAP.load(deps.storage, source.to_owned())?;
+    for addr in add_list.unwrap_or_default() {
+        let addr = normalize_denied_address(deps.api, &addr)?;
+        DENYLIST.save(deps.storage, addr, &true)?;
+    }
+    for addr in remove_list.unwrap_or_default() {
+        let addr = normalize_denied_address(deps.api, &addr)?;
+        DENYLIST.remove(deps.storage, addr);
+    }
 
-        // Ensure mapping is valid before removing keys
-        if existing_dest != pair.destination {
-            return Err(ContractError::MappingNotFound {
-                source_addr: source.into_string(),
-            });
+    match contract.as_deref() {
+        Some("") => DENYLIST_CONTRACT.remove(deps.storage),
+        Some(addr) => {
+            let contract_valid = deps.api.addr_validate(addr)?;
+            DENYLIST_CONTRACT.save(deps.storage, &contract_valid)?;
         }
-        TERRA_TO_SN_MAP.remove(deps.storage, source);
-        SN_TO_TERRA_MAP.remove(deps.storage, pair.destination);
+        None => {}
     }
 
-    // Create new mapping in storage for each CollectionMapping
-    for pair in add_list.unwrap_or_default() {
-        let source = deps.api.addr_validate(&pair.source)?;
-        let dest = pair.destination;
-        TERRA_TO_SN_MAP.update(deps.storage, source.to_owned(), |existing| match existing {
-            // Do not allow key overwrites
-            Some(_) => Err(ContractError::MappingExists {
-                source_addr: source.to_owned().into_string(),
-            }),
-            None => Ok(dest.to_owned()),
-        })?;
-        SN_TO_TERRA_MAP.update(deps.storage, dest.to_owned(), |existing| match existing {
-            // Do not allow key overwrites
-            Some(_) => Err(ContractError::MappingExists { source_addr: dest }),
-            None => Ok(source),
-        })?;
+    Ok(Response::default().add_attribute("action", "update_denylist"))
+}
+
+/// Allows admins to set the sliding-window rate limits on receives and releases.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `limits` - the new limits. `None` removes all limits
+pub fn try_update_rate_limits(
+    deps: DepsMut,
+    info: MessageInfo,
+    limits: Option<RateLimits>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
     }
 
-    Ok(Response::default().add_attribute("action", "update_collection_mappings"))
+    match limits {
+        Some(limits) => RATE_LIMITS.save(deps.storage, &limits)?,
+        None => RATE_LIMITS.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_rate_limits"))
 }
 
-/// Allows operators to release NFTs from bridge escrow.
+/// Allows admins to time-lock releases from a collection. Releases from a time-locked
+/// collection are queued and can only be executed once `delay` seconds have passed.
 ///
 /// # Arguments
 ///
 /// * `deps` - Extern containing all the contract's external dependencies
-/// * `env` - Env of the contract's environment
 /// * `info` - additional information about the message sender and attached funds
-/// * `sn_coll_addr` - the SN collection's address
-/// * `sn_sender` - the SN address that bridged the NFT
-/// * `coll_addr` - the Terra collection's address
-/// * `recipient` - the Terra address receiving the bridged NFTs
-/// * `token_id` - id of the token being bridged
-#[allow(clippy::too_many_arguments)]
-pub fn try_release_nft(
+/// * `collection` - the Terra collection's address
+/// * `delay` - the number of seconds releases are queued for. `None` removes the time-lock
+pub fn try_update_release_delay(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
-    sn_coll_addr: String,
-    sn_sender: String,
-    recipient: String,
-    token_id: String,
-    recipient_is_contract: bool,
+    collection: String,
+    delay: Option<u64>,
 ) -> Result<Response, ContractError> {
-    // Check if sender is an operator or admin
+    // Verify sender is an admin
     let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
-    if !check_is_operator(deps.storage, sender_raw)? {
+    if !check_is_admin(deps.storage, sender_raw)? {
         return Err(ContractError::Unauthorized {});
     }
 
-    let recipient_valid = deps.api.addr_validate(&recipient)?;
-    let terra_collection = SN_TO_TERRA_MAP.load(deps.storage, sn_coll_addr.to_owned())?;
+    let coll_valid = deps.api.addr_validate(&collection)?;
+    match delay {
+        Some(delay) => RELEASE_DELAYS.save(deps.storage, coll_valid, &delay)?,
+        None => RELEASE_DELAYS.remove(deps.storage, coll_valid),
+    }
 
-    // Check if the bridge is paused
-    let is_paused = check_is_paused(deps.storage, terra_collection.to_owned())?;
-    if is_paused {
-        return Err(ContractError::BridgePaused {});
+    Ok(Response::new()
+        .add_attribute("action", "update_release_delay")
+        .add_attribute("terra_collection", collection))
+}
+
+/// Allows admins to queue every release for a challenge period, during which
+/// watchers can challenge it. The period applies to every collection, so while it is set
+/// no collection can make tokens claimable.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `period` - the number of seconds releases are queued for. `None` ends optimistic releases
+pub fn try_update_challenge_period(
+    deps: DepsMut,
+    info: MessageInfo,
+    period: Option<u64>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
     }
 
-    // Create & save history
-    let record = BridgeRecord {
-        is_enter: false,
-        token_id: token_id.to_owned(),
-        source_address: Some(recipient_valid),
-        source_collection: terra_collection.to_owned(),
-        destination_address: Some(sn_sender.to_owned()),
-        destination_collection: sn_coll_addr.to_owned(),
-        block_height: env.block.height,
-        block_time: env.block.time.seconds(),
-    };
-    let history_id = save_history(
-        deps.storage,
-        terra_collection.to_owned(),
-        token_id.to_owned(),
-        record,
-    )?;
+    match period {
+        Some(period) => CHALLENGE_PERIOD.save(deps.storage, &period)?,
+        None => CHALLENGE_PERIOD.remove(deps.storage),
+    }
 
-    // Create the "fire and forget" message to transfer ownership
-    let msg = if recipient_is_contract {
-        SendNft {
-            contract: recipient.to_owned(),
-            token_id: token_id.to_owned(),
-            msg: Binary::from(vec![]),
+    Ok(Response::new().add_attribute("action", "update_challenge_period"))
+}
+
+/// Allows admins to set how operators bond funds and how their bonds are slashed.
+/// Once set, operators are only active while their bond meets `min_bond`. Admins can always
+/// act as operators, bonded or not.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `config` - the new configuration. `None` disables bonding once nothing is bonded
+pub fn try_update_bond_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: Option<BondConfigMsg>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Bonded funds can only be unbonded, claimed, and protected in the denom they were paid in
+    let bonded = TOTAL_BONDED.may_load(deps.storage)?.unwrap_or_default();
+    if let Some(current) = BOND_CONFIG.may_load(deps.storage)? {
+        let denom = config.as_ref().map(|config| config.denom.as_str());
+        if !bonded.is_zero() && denom != Some(current.denom.as_str()) {
+            return Err(ContractError::BondsOutstanding {});
         }
-    } else {
-        TransferNft {
-            recipient: recipient.to_owned(),
-            token_id: token_id.to_owned(),
+    }
+
+    match config {
+        Some(config) => {
+            let config = BondConfig {
+                denom: config.denom,
+                min_bond: config.min_bond,
+                unbonding_period: config.unbonding_period,
+                treasury: deps.api.addr_validate(&config.treasury)?,
+                slash_approvals: config.slash_approvals,
+            };
+            BOND_CONFIG.save(deps.storage, &config)?
         }
-    };
+        None => BOND_CONFIG.remove(deps.storage),
+    }
 
-    let send = WasmMsg::Execute {
-        contract_addr: terra_collection.to_owned().into(),
-        msg: to_binary(&msg)?,
-        funds: vec![],
-    };
+    Ok(Response::new().add_attribute("action", "update_bond_config"))
+}
 
-    Ok(Response::new()
-        .add_message(send)
-        .add_attribute("action", "transfer_nft")
-        .add_attribute("secret_sender", sn_sender)
-        .add_attribute("recipient", recipient)
-        .add_attribute("terra_collection", terra_collection)
-        .add_attribute("secret_collection", sn_coll_addr)
-        .add_attribute("token_id", token_id)
-        .add_attribute("history_id", history_id.to_string()))
+/// Allows admins to propose slashing an operator's bond. The proposal counts as the
+/// sender's approval and replaces any earlier proposal against the operator.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `operator` - the operator's address
+/// * `amount` - the amount to slash
+/// * `reason` - the bad release that justifies the slash
+pub fn try_propose_slash(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+    amount: Uint128,
+    reason: String,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let operator_raw = deps.api.addr_canonicalize(&operator)?;
+    let proposal = SlashProposal {
+        amount,
+        reason,
+        approvals: vec![info.sender],
+    };
+    let response = Response::new()
+        .add_attribute("action", "propose_slash")
+        .add_attribute("operator", operator);
+    apply_slash(deps.storage, operator_raw, proposal, response)
 }
 
-/// Allows operators to release NFTs from bridge escrow.
+/// Allows admins to approve the slash proposed against an operator.
 ///
 /// # Arguments
 ///
 /// * `deps` - Extern containing all the contract's external dependencies
-/// * `env` - Env of the contract's environment
 /// * `info` - additional information about the message sender and attached funds
-/// * `sender` - the Terra address bridging the NFT (from Cw721ReceiveMsg)
-/// * `token_id` - id of the token being bridged
-pub fn try_receive_nft(
+/// * `operator` - the operator's address
+pub fn try_approve_slash(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
-    sender: String,
-    token_id: String,
-    msg: Binary,
+    operator: String,
 ) -> Result<Response, ContractError> {
-    // Check if the bridge is paused
-    let is_paused = check_is_paused(deps.storage, info.sender.to_owned())?;
-    if is_paused {
-        return Err(ContractError::BridgePaused {});
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
     }
 
-    // Validate NFT sender
-    let sender_addr = deps.api.addr_validate(&sender)?;
+    let operator_raw = deps.api.addr_canonicalize(&operator)?;
+    let mut proposal = SLASH_PROPOSALS
+        .may_load(deps.storage, operator_raw.to_vec())?
+        .ok_or(ContractError::SlashNotProposed {
+            operator: operator.to_owned(),
+        })?;
+    if proposal.approvals.contains(&info.sender) {
+        return Err(ContractError::SlashAlreadyApproved {});
+    }
+    proposal.approvals.push(info.sender);
 
-    // Check whitelist to see if the collection is mapped to Secret
-    let sn_coll_addr = TERRA_TO_SN_MAP
-        .may_load(deps.storage, info.sender.to_owned())?
-        .ok_or(ContractError::UnauthorizedCollection {})?;
-    let sn_sender: String = from_binary(&msg)?;
+    let response = Response::new()
+        .add_attribute("action", "approve_slash")
+        .add_attribute("operator", operator);
+    apply_slash(deps.storage, operator_raw, proposal, response)
+}
 
-    // Save history
-    let record = BridgeRecord {
-        token_id: token_id.to_owned(),
-        is_enter: true,
+/// Saves the slash proposal, or slashes the operator's bond to the treasury once enough
+/// admins have approved it. Bonded funds are slashed before unbonding funds.
+fn apply_slash(
+    store: &mut dyn Storage,
+    operator_raw: CanonicalAddr,
+    proposal: SlashProposal,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let config = BOND_CONFIG
+        .may_load(store)?
+        .ok_or(ContractError::BondingDisabled {})?;
+    if (proposal.approvals.len() as u64) < config.slash_approvals as u64 {
+        SLASH_PROPOSALS.save(store, operator_raw.to_vec(), &proposal)?;
+        return Ok(response.add_attribute("approvals", proposal.approvals.len().to_string()));
+    }
+    SLASH_PROPOSALS.remove(store, operator_raw.to_vec());
+
+    let mut bond = BONDS
+        .may_load(store, operator_raw.to_vec())?
+        .unwrap_or_default();
+    let slashed = proposal.amount.min(bond.total());
+    let from_bond = slashed.min(bond.amount);
+    bond.amount -= from_bond;
+    bond.unbonding -= slashed - from_bond;
+    save_bond(store, operator_raw, bond)?;
+    TOTAL_BONDED.update(store, |total| -> StdResult<_> { Ok(total - slashed) })?;
+
+    let mut response = response
+        .add_attribute("slashed", slashed.to_string())
+        .add_attribute("reason", proposal.reason);
+    if !slashed.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: config.treasury.into_string(),
+            amount: vec![Coin {
+                denom: config.denom,
+                amount: slashed,
+            }],
+        });
+    }
+    Ok(response)
+}
+
+/// Saves the bond, removing it once it holds no funds
+fn save_bond(store: &mut dyn Storage, operator_raw: CanonicalAddr, bond: Bond) -> StdResult<()> {
+    if bond.total().is_zero() {
+        BONDS.remove(store, operator_raw.to_vec());
+        return Ok(());
+    }
+    BONDS.save(store, operator_raw.to_vec(), &bond)
+}
+
+/// Allows admins to stop the bridge from receiving tokens when no operator has sent
+/// a heartbeat within `window` seconds.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `window` - the number of seconds. `None` receives tokens regardless of heartbeats
+pub fn try_update_liveness_window(
+    deps: DepsMut,
+    info: MessageInfo,
+    window: Option<u64>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match window {
+        Some(window) => LIVENESS_WINDOW.save(deps.storage, &window)?,
+        None => LIVENESS_WINDOW.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_liveness_window"))
+}
+
+/// Allows admins to set the conditions under which the bridge pauses itself.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `circuit_breaker` - the new trip conditions. `None` disables the circuit breaker
+pub fn try_update_circuit_breaker(
+    deps: DepsMut,
+    info: MessageInfo,
+    circuit_breaker: Option<CircuitBreaker>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match circuit_breaker {
+        Some(circuit_breaker) => CIRCUIT_BREAKER.save(deps.storage, &circuit_breaker)?,
+        None => CIRCUIT_BREAKER.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_circuit_breaker"))
+}
+
+/// Allows admins to set how long a received token must remain unacknowledged
+/// before its depositor can reclaim it.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `timeout` - the new timeout, in seconds
+pub fn try_update_reclaim_timeout(
+    deps: DepsMut,
+    info: MessageInfo,
+    timeout: u64,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    RECLAIM_TIMEOUT.save(deps.storage, &timeout)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_reclaim_timeout")
+        .add_attribute("new_value", timeout.to_string()))
+}
+
+/// Allows admins to permanently wind down the bridge. The bridge stops receiving tokens and
+/// each depositor can withdraw the tokens they sent to the bridge. This can not be undone.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+pub fn try_wind_down(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if WOUND_DOWN_AT.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::BridgeWoundDown {});
+    }
+    WOUND_DOWN_AT.save(deps.storage, &env.block.time.seconds())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "wind_down")
+        .add_attribute("sender", info.sender))
+}
+
+/// Updates the collection mappings in storage.
+/// All items in `rem_list` are removed before adding items from `add_list`.
+/// * Sender must be an admin or operator
+/// * Sources in `add_list` must answer the cw721 `ContractInfo` and `NumTokens` queries
+///
+/// # Arguments
+///
+/// * `deps` - a mutable reference to Extern containing all the contract's external dependencies
+/// * `info` - additional information about the contract's caller
+/// * `add_list` - a list of [CollectionMapping]s to be stored
+/// * `rem_list` - a list of Terra contract addresses to be cleared from storage
+pub fn try_update_collection_mappings(
+    deps: DepsMut,
+    info: MessageInfo,
+    rem_list: Option<Vec<CollectionMapping>>,
+    add_list: Option<Vec<CollectionMapping>>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an operator
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Remove items first so we can safely update a key's mapping in one message
+    for pair in rem_list.unwrap_or_default() {
+        let source = deps.api.addr_validate(&pair.source)?;
+        let existing_dest = TERRA_TO_SN_MAP.load(deps.storage, source.to_owned())?;
+
+        // Ensure mapping is valid before removing keys
+        if existing_dest != pair.destination {
+            return Err(ContractError::MappingNotFound {
+                source_addr: source.into_string(),
+            });
+        }
+        TERRA_TO_SN_MAP.remove(deps.storage, source.to_owned());
+        SN_TO_TERRA_MAP.remove(deps.storage, pair.destination);
+        COLLECTION_INFO.remove(deps.storage, source.to_owned());
+        ALLOWLIST_ONLY.remove(deps.storage, source);
+    }
+
+    // Create new mapping in storage for each CollectionMapping
+    for pair in add_list.unwrap_or_default() {
+        let source = deps.api.addr_validate(&pair.source)?;
+        let dest = pair.destination;
+
+        // Only allow mappings for contracts that answer as cw721 collections
+        let coll_info = query_collection_info(&deps.querier, source.to_owned()).map_err(|_| {
+            ContractError::InvalidCollection {
+                address: source.to_string(),
+            }
+        })?;
+        COLLECTION_INFO.save(deps.storage, source.to_owned(), &coll_info)?;
+        if pair.allowlist_only.unwrap_or(false) {
+            ALLOWLIST_ONLY.save(deps.storage, source.to_owned(), &true)?;
+        }
+
+        TERRA_TO_SN_MAP.update(deps.storage, source.to_owned(), |existing| match existing {
+            // Do not allow key overwrites
+            Some(_) => Err(ContractError::MappingExists {
+                source_addr: source.to_owned().into_string(),
+            }),
+            None => Ok(dest.to_owned()),
+        })?;
+        SN_TO_TERRA_MAP.update(deps.storage, dest.to_owned(), |existing| match existing {
+            // Do not allow key overwrites
+            Some(_) => Err(ContractError::MappingExists { source_addr: dest }),
+            None => Ok(source),
+        })?;
+    }
+
+    Ok(Response::default().add_attribute("action", "update_collection_mappings"))
+}
+
+/// Updates the Terra addresses that can bridge a collection's NFTs in allowlist-only mode.
+/// * Sender must be an admin or operator, and an admin to change the mode
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `collection` - the Terra collection's address
+/// * `add_list` - the addresses to allowlist
+/// * `remove_list` - the addresses to remove from the allowlist
+/// * `allowlist_only` - optionally turns allowlist-only mode on or off for the collection
+pub fn try_update_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    add_list: Option<Vec<String>>,
+    remove_list: Option<Vec<String>>,
+    allowlist_only: Option<bool>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an operator
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw.to_owned())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let coll_addr = deps.api.addr_validate(&collection)?;
+    if let Some(allowlist_only) = allowlist_only {
+        // Only admins can change the mode
+        if !check_is_admin(deps.storage, sender_raw)? {
+            return Err(ContractError::Unauthorized {});
+        }
+        if !TERRA_TO_SN_MAP.has(deps.storage, coll_addr.to_owned()) {
+            return Err(ContractError::MappingNotFound {
+                source_addr: collection,
+            });
+        }
+        if allowlist_only {
+            ALLOWLIST_ONLY.save(deps.storage, coll_addr.to_owned(), &true)?;
+        } else {
+            ALLOWLIST_ONLY.remove(deps.storage, coll_addr.to_owned());
+        }
+    }
+    for addr in add_list.unwrap_or_default() {
+        let addr_valid = deps.api.addr_validate(&addr)?;
+        ALLOWLIST.save(deps.storage, (coll_addr.to_owned(), addr_valid), &true)?;
+    }
+    for addr in remove_list.unwrap_or_default() {
+        let addr_valid = deps.api.addr_validate(&addr)?;
+        ALLOWLIST.remove(deps.storage, (coll_addr.to_owned(), addr_valid));
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_allowlist")
+        .add_attribute("terra_collection", coll_addr))
+}
+
+/// Allows operators to release NFTs from bridge escrow.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `sn_coll_addr` - the SN collection's address
+/// * `sn_sender` - the SN address that bridged the NFT
+/// * `coll_addr` - the Terra collection's address
+/// * `recipient` - the Terra address receiving the bridged NFTs
+/// * `token_id` - id of the token being bridged
+/// * `recipient_is_contract` - overrides whether the NFT is delivered with `SendNft`
+/// * `payload` - optional message forwarded to a contract recipient
+/// * `checkpoint` - optional SN position the sender has fully processed once the NFT is released.
+///   Not recorded if the circuit breaker trips
+#[allow(clippy::too_many_arguments)]
+pub fn try_release_nft(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sn_coll_addr: String,
+    sn_sender: String,
+    recipient: String,
+    token_id: String,
+    recipient_is_contract: Option<bool>,
+    payload: Option<Binary>,
+    checkpoint: Option<Checkpoint>,
+) -> Result<Response, ContractError> {
+    // Check if sender is an operator or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let payload_hash = payload.as_ref().map(|payload| sha_256(payload.as_slice()));
+    let result = prepare_release(
+        &mut deps,
+        &env,
+        &info.sender,
+        sn_coll_addr.to_owned(),
+        sn_sender.to_owned(),
+        recipient.to_owned(),
+        token_id.to_owned(),
+        recipient_is_contract,
+        payload,
+    );
+    let (pending, send) = match result {
+        // The pause must be saved, so a trip succeeds without releasing the token
+        Err(ContractError::CircuitBreakerTripped { scope, reason }) => {
+            return Ok(Response::new()
+                .add_event(tripped_event(&scope, &reason))
+                .add_attribute("action", "release_tripped")
+                .add_attribute("secret_sender", sn_sender)
+                .add_attribute("recipient", recipient)
+                .add_attribute("secret_collection", sn_coll_addr)
+                .add_attribute("token_id", token_id)
+                .add_attribute("scope", scope)
+                .add_attribute("reason", reason)
+                .add_attributes(history_chain_attributes(deps.storage)?));
+        }
+        Ok(PreparedRelease::Queued {
+            queue_id,
+            unlock_at,
+        }) => {
+            let response = Response::new()
+                .add_attribute("action", "queue_release")
+                .add_attribute("secret_sender", sn_sender)
+                .add_attribute("recipient", recipient)
+                .add_attribute("secret_collection", sn_coll_addr)
+                .add_attribute("token_id", token_id)
+                .add_attribute("queue_id", queue_id.to_string())
+                .add_attribute("unlock_at", unlock_at.to_string());
+            return apply_checkpoint(deps.storage, &info.sender, checkpoint, response);
+        }
+        Ok(PreparedRelease::Dispatched(pending, send)) => (pending, send),
+        Err(err) => return Err(err),
+    };
+    let terra_collection = pending.collection.to_owned();
+    let history_id = pending.history_id;
+
+    let reply_id = save_pending_release(deps.storage, &pending)?;
+
+    let mut response = Response::new()
+        .add_submessage(SubMsg::reply_always(send, reply_id))
+        .add_attribute("action", "transfer_nft")
+        .add_attribute("secret_sender", sn_sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("terra_collection", terra_collection)
+        .add_attribute("secret_collection", sn_coll_addr)
+        .add_attribute("token_id", token_id)
+        .add_attribute("history_id", history_id.to_string())
+        .add_attributes(history_chain_attributes(deps.storage)?);
+    if let Some(hash) = payload_hash {
+        response = response.add_attribute("payload_hash", hash.to_base64());
+    }
+    apply_checkpoint(deps.storage, &info.sender, checkpoint, response)
+}
+
+/// Allows operators to release many NFTs from bridge escrow in one message.
+/// The batch fails as a whole if any release is invalid, unless `skip_invalid`
+/// is set, in which case invalid releases are skipped and reported in the response data.
+/// A circuit breaker trip ends the batch, but releases made before the trip are still
+/// dispatched since they passed every check, including the breaker's.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `releases` - a list of [ReleaseRequest] structures to release
+/// * `skip_invalid` - `true` to skip invalid releases instead of failing the batch
+/// * `checkpoint` - optional SN position the sender has fully processed once the NFTs are released.
+///   Not recorded if any release is skipped
+pub fn try_release_nfts(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    releases: Vec<ReleaseRequest>,
+    skip_invalid: Option<bool>,
+    checkpoint: Option<Checkpoint>,
+) -> Result<Response, ContractError> {
+    // Check if sender is an operator or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let skip_invalid = skip_invalid.unwrap_or(false);
+    let mut seen: BTreeSet<(String, String)> = BTreeSet::new();
+    let mut released = 0u32;
+    let mut skipped: Vec<SkippedRelease> = vec![];
+    let mut queued = 0u32;
+    let mut tripped = false;
+    let mut response = Response::new().add_attribute("action", "release_nfts");
+
+    let mut releases = releases.into_iter().enumerate();
+    for (index, release) in releases.by_ref() {
+        let token_id = release.token_id.to_owned();
+
+        // A token can only be released once per batch
+        let is_new = seen.insert((release.sn_collection.to_owned(), token_id.to_owned()));
+        let result = if !is_new {
+            Err(ContractError::DuplicateRelease {
+                token_id: token_id.to_owned(),
+            })
+        } else {
+            prepare_release(
+                &mut deps,
+                &env,
+                &info.sender,
+                release.sn_collection,
+                release.sn_address,
+                release.recipient,
+                release.token_id,
+                release.recipient_is_contract,
+                release.msg,
+            )
+        };
+
+        match result {
+            Ok(PreparedRelease::Dispatched(pending, send)) => {
+                let reply_id = save_pending_release(deps.storage, &pending)?;
+                response = response
+                    .add_submessage(SubMsg::reply_always(send, reply_id))
+                    .add_attribute("history_id", pending.history_id.to_string());
+                released += 1;
+            }
+            Ok(PreparedRelease::Queued { queue_id, .. }) => {
+                response = response.add_attribute("queue_id", queue_id.to_string());
+                queued += 1;
+            }
+            // The pause must be saved, so a trip ends the batch without failing it
+            Err(ContractError::CircuitBreakerTripped { scope, reason }) => {
+                response = response.add_event(tripped_event(&scope, &reason));
+                tripped = true;
+                skipped.push(SkippedRelease {
+                    index: index as u32,
+                    token_id,
+                    error: ContractError::CircuitBreakerTripped { scope, reason }.to_string(),
+                });
+                if !skip_invalid {
+                    break;
+                }
+            }
+            Err(err) if skip_invalid => skipped.push(SkippedRelease {
+                index: index as u32,
+                token_id,
+                error: err.to_string(),
+            }),
+            Err(err) => return Err(err),
+        }
+    }
+
+    // Releases after a trip that ended the batch are not processed
+    if let Some(trip) = skipped
+        .last()
+        .filter(|_| tripped)
+        .map(|skip| skip.error.to_owned())
+    {
+        for (index, release) in releases {
+            skipped.push(SkippedRelease {
+                index: index as u32,
+                token_id: release.token_id,
+                error: trip.to_owned(),
+            });
+        }
+    }
+
+    let skipped_any = !skipped.is_empty();
+    response = response
+        .add_attribute("released", released.to_string())
+        .add_attribute("queued", queued.to_string())
+        .add_attribute("skipped", skipped.len().to_string())
+        .set_data(to_binary(&ReleaseNftsResponse { skipped })?);
+    if released > 0 {
+        response = response.add_attributes(history_chain_attributes(deps.storage)?);
+    }
+
+    // Relayers must retry skipped releases, so the SN position has not been fully processed
+    match checkpoint {
+        Some(_) if skipped_any => Ok(response),
+        checkpoint => apply_checkpoint(deps.storage, &info.sender, checkpoint, response),
+    }
+}
+
+/// The outcome of a valid release
+#[allow(clippy::large_enum_variant)]
+enum PreparedRelease {
+    /// The token is delivered by the returned message
+    Dispatched(PendingRelease, WasmMsg),
+    /// The token is held in the time-lock queue
+    Queued { queue_id: u64, unlock_at: u64 },
+}
+
+/// Validates a single release and dispatches or queues it. Nothing is written to storage
+/// for an invalid release, except that a circuit breaker trip saves its pause.
+#[allow(clippy::too_many_arguments)]
+fn prepare_release(
+    deps: &mut DepsMut,
+    env: &Env,
+    operator: &Addr,
+    sn_coll_addr: String,
+    sn_sender: String,
+    recipient: String,
+    token_id: String,
+    recipient_is_contract: Option<bool>,
+    payload: Option<Binary>,
+) -> Result<PreparedRelease, ContractError> {
+    let recipient_valid = deps.api.addr_validate(&recipient)?;
+    check_not_denied(deps.as_ref(), &[recipient_valid.as_str(), &sn_sender])?;
+
+    // Determine how the NFT will be delivered unless the operator overrides it
+    let recipient_is_contract = match recipient_is_contract {
+        Some(is_contract) => is_contract,
+        None => check_is_contract(&deps.querier, &recipient_valid)?,
+    };
+    let delivery_mode = if recipient_is_contract {
+        DeliveryMode::Send
+    } else {
+        DeliveryMode::Transfer
+    };
+
+    // Validate the payload forwarded to contract recipients
+    if let Some(payload) = &payload {
+        if !recipient_is_contract {
+            return Err(ContractError::PayloadRequiresContract {});
+        }
+        if payload.len() > MAX_PAYLOAD_SIZE {
+            return Err(ContractError::PayloadTooLarge {
+                size: payload.len(),
+                max: MAX_PAYLOAD_SIZE,
+            });
+        }
+    }
+    let payload_hash = payload.as_ref().map(|payload| sha_256(payload.as_slice()));
+
+    let terra_collection = SN_TO_TERRA_MAP.load(deps.storage, sn_coll_addr.to_owned())?;
+
+    // Check if the bridge is paused
+    let is_paused = check_is_paused(deps.storage, terra_collection.to_owned())?;
+    if is_paused {
+        return Err(ContractError::BridgePaused {});
+    }
+
+    check_not_frozen(deps.storage, &terra_collection, &token_id)?;
+    check_not_queued(deps.storage, &terra_collection, &token_id)?;
+    check_in_custody(deps, env, &terra_collection, &token_id)?;
+    check_not_claimable(deps.storage, env, &terra_collection, &token_id)?;
+
+    // Check both limits before recording either, so a rejected release leaves no usage behind
+    check_rate_limit(deps.storage, env, RateScope::Operator, operator.as_str())?;
+    check_rate_limit(
+        deps.storage,
+        env,
+        RateScope::Collection,
+        terra_collection.as_str(),
+    )?;
+
+    // Only releases that would otherwise go ahead can trip the circuit breaker
+    check_circuit_breaker(deps.storage, env, &terra_collection, &token_id)?;
+    record_rate_limit(deps.storage, env, RateScope::Operator, operator.as_str())?;
+    record_rate_limit(
+        deps.storage,
+        env,
+        RateScope::Collection,
+        terra_collection.as_str(),
+    )?;
+    record_breaker_usage(deps.storage, env, &terra_collection)?;
+
+    // Time-locked and optimistic releases wait in the queue until they unlock
+    if let Some(delay) = release_delay(deps.storage, &terra_collection)? {
+        let queued_at = env.block.time.seconds();
+        let unlock_at = queued_at + delay;
+        let deposit = latest_inbound_history(
+            deps.storage,
+            terra_collection.to_owned(),
+            token_id.to_owned(),
+        )?;
+        let release = QueuedRelease {
+            collection: terra_collection,
+            token_id,
+            recipient: recipient_valid,
+            sn_collection: sn_coll_addr,
+            sn_address: sn_sender,
+            delivery_mode,
+            payload,
+            deposit_history_id: deposit.map(|(history_id, _)| history_id),
+            queued_at,
+            unlock_at,
+            challenge: None,
+        };
+        let queue_id = save_queued_release(deps.storage, release)?;
+        return Ok(PreparedRelease::Queued {
+            queue_id,
+            unlock_at,
+        });
+    }
+
+    let (pending, send) = dispatch_release(
+        deps.storage,
+        env,
+        terra_collection,
+        sn_coll_addr,
+        sn_sender,
+        recipient_valid,
+        token_id,
+        delivery_mode,
+        payload,
+        payload_hash,
+    )?;
+    Ok(PreparedRelease::Dispatched(pending, send))
+}
+
+/// Allows anyone to execute a queued release once it has unlocked.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `queue_id` - the id of the queued release
+pub fn try_execute_queued_release(
+    deps: DepsMut,
+    env: Env,
+    queue_id: u64,
+) -> Result<Response, ContractError> {
+    let release = QUEUED_RELEASES
+        .may_load(deps.storage, queue_id.into())?
+        .ok_or(ContractError::QueuedReleaseNotFound { queue_id })?;
+    if release.challenge.is_some() {
+        return Err(ContractError::ReleaseChallenged { queue_id });
+    }
+    if env.block.time.seconds() < release.unlock_at {
+        return Err(ContractError::ReleaseLocked {
+            queue_id,
+            unlock_at: release.unlock_at,
+        });
+    }
+
+    check_not_denied(
+        deps.as_ref(),
+        &[release.recipient.as_str(), &release.sn_address],
+    )?;
+
+    // Check if the bridge is paused
+    let is_paused = check_is_paused(deps.storage, release.collection.to_owned())?;
+    if is_paused {
+        return Err(ContractError::BridgePaused {});
+    }
+
+    check_not_frozen(deps.storage, &release.collection, &release.token_id)?;
+    check_in_custody(&deps, &env, &release.collection, &release.token_id)?;
+
+    // The token must still be escrowed for the deposit the release was queued for
+    if let Some(deposit_history_id) = release.deposit_history_id {
+        let latest = latest_inbound_history(
+            deps.storage,
+            release.collection.to_owned(),
+            release.token_id.to_owned(),
+        )?;
+        if latest.map(|(history_id, _)| history_id) != Some(deposit_history_id) {
+            return Err(ContractError::QueuedDepositChanged { queue_id });
+        }
+    }
+    remove_queued_release(deps.storage, queue_id, &release);
+
+    let payload_hash = release
+        .payload
+        .as_ref()
+        .map(|payload| sha_256(payload.as_slice()));
+    let (pending, send) = dispatch_release(
+        deps.storage,
+        &env,
+        release.collection.to_owned(),
+        release.sn_collection,
+        release.sn_address,
+        release.recipient.to_owned(),
+        release.token_id.to_owned(),
+        release.delivery_mode,
+        release.payload,
+        payload_hash,
+    )?;
+    let history_id = pending.history_id;
+
+    let reply_id = save_pending_release(deps.storage, &pending)?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_always(send, reply_id))
+        .add_attribute("action", "execute_queued_release")
+        .add_attribute("queue_id", queue_id.to_string())
+        .add_attribute("recipient", release.recipient)
+        .add_attribute("terra_collection", release.collection)
+        .add_attribute("token_id", release.token_id)
+        .add_attribute("history_id", history_id.to_string())
+        .add_attributes(history_chain_attributes(deps.storage)?))
+}
+
+/// Allows operators to bond the attached funds.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+pub fn try_bond(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = BOND_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::BondingDisabled {})?;
+
+    // Only operators can bond
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !OPERS.load(deps.storage)?.contains(&sender_raw) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == config.denom && !coin.amount.is_zero() => coin.amount,
+        _ => {
+            return Err(ContractError::InvalidBond {
+                denom: config.denom,
+            })
+        }
+    };
+    let mut bond = BONDS
+        .may_load(deps.storage, sender_raw.to_vec())?
+        .unwrap_or_default();
+    bond.amount += amount;
+    save_bond(deps.storage, sender_raw, bond.to_owned())?;
+    let total_bonded = TOTAL_BONDED.may_load(deps.storage)?.unwrap_or_default();
+    TOTAL_BONDED.save(deps.storage, &(total_bonded + amount))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "bond")
+        .add_attribute("operator", info.sender)
+        .add_attribute("bonded", bond.amount.to_string()))
+}
+
+/// Allows former operators to start unbonding their funds. The funds can be slashed until
+/// they are claimed, and unbonding again restarts the unbonding period.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `amount` - the amount to unbond
+pub fn try_unbond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = BOND_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::BondingDisabled {})?;
+
+    // The bond is locked while the sender holds the operator role
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if OPERS.load(deps.storage)?.contains(&sender_raw) {
+        return Err(ContractError::BondLocked {});
+    }
+
+    let mut bond = BONDS
+        .may_load(deps.storage, sender_raw.to_vec())?
+        .unwrap_or_default();
+    if amount.is_zero() || amount > bond.amount {
+        return Err(ContractError::InsufficientBond {
+            available: bond.amount,
+        });
+    }
+    let unbonded_at = env.block.time.seconds() + config.unbonding_period;
+    bond.amount -= amount;
+    bond.unbonding += amount;
+    bond.unbonded_at = Some(unbonded_at);
+    save_bond(deps.storage, sender_raw, bond)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unbond")
+        .add_attribute("operator", info.sender)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("unbonded_at", unbonded_at.to_string()))
+}
+
+/// Allows former operators to claim funds that have finished unbonding.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+pub fn try_claim_bond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = BOND_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::BondingDisabled {})?;
+
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut bond = BONDS
+        .may_load(deps.storage, sender_raw.to_vec())?
+        .unwrap_or_default();
+    let unbonded_at = match bond.unbonded_at {
+        Some(unbonded_at) if !bond.unbonding.is_zero() => unbonded_at,
+        _ => return Err(ContractError::NothingToClaim {}),
+    };
+    if env.block.time.seconds() < unbonded_at {
+        return Err(ContractError::UnbondingNotReady {
+            available_at: unbonded_at,
+        });
+    }
+
+    let amount = bond.unbonding;
+    bond.unbonding = Uint128::zero();
+    bond.unbonded_at = None;
+    save_bond(deps.storage, sender_raw, bond)?;
+    TOTAL_BONDED.update(deps.storage, |total| -> StdResult<_> { Ok(total - amount) })?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin {
+                denom: config.denom,
+                amount,
+            }],
+        })
+        .add_attribute("action", "claim_bond")
+        .add_attribute("operator", info.sender)
+        .add_attribute("amount", amount.to_string()))
+}
+
+/// Allows watchers and admins to veto a queued release. The release can not be executed
+/// and its collection is paused until an admin steps in.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `queue_id` - the id of the queued release
+/// * `reason` - why the release is being challenged
+pub fn try_challenge_release(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    queue_id: u64,
+    reason: String,
+) -> Result<Response, ContractError> {
+    // Check if sender is a watcher or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_watcher(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut release = QUEUED_RELEASES
+        .may_load(deps.storage, queue_id.into())?
+        .ok_or(ContractError::QueuedReleaseNotFound { queue_id })?;
+    if release.challenge.is_some() {
+        return Err(ContractError::ReleaseChallenged { queue_id });
+    }
+    // Anyone can execute the release once it unlocks, so a late challenge would race them
+    if env.block.time.seconds() >= release.unlock_at {
+        return Err(ContractError::ChallengePeriodEnded {
+            queue_id,
+            unlock_at: release.unlock_at,
+        });
+    }
+    release.challenge = Some(Challenge {
+        watcher: info.sender.to_owned(),
+        reason: reason.to_owned(),
+        challenged_at: env.block.time.seconds(),
+    });
+    QUEUED_RELEASES.save(deps.storage, queue_id.into(), &release)?;
+    set_paused(deps.storage, Some(&release.collection), true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "challenge_release")
+        .add_attribute("queue_id", queue_id.to_string())
+        .add_attribute("watcher", info.sender)
+        .add_attribute("terra_collection", release.collection)
+        .add_attribute("token_id", release.token_id)
+        .add_attribute("reason", reason))
+}
+
+/// Allows admins to dismiss a watcher's challenge so the queued release can be executed.
+/// The collection stays paused until an admin unpauses it.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `queue_id` - the id of the queued release
+pub fn try_dismiss_challenge(
+    deps: DepsMut,
+    info: MessageInfo,
+    queue_id: u64,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut release = QUEUED_RELEASES
+        .may_load(deps.storage, queue_id.into())?
+        .ok_or(ContractError::QueuedReleaseNotFound { queue_id })?;
+    if release.challenge.take().is_none() {
+        return Err(ContractError::ReleaseNotChallenged { queue_id });
+    }
+    QUEUED_RELEASES.save(deps.storage, queue_id.into(), &release)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "dismiss_challenge")
+        .add_attribute("queue_id", queue_id.to_string()))
+}
+
+/// Allows guardians and admins to cancel a queued release before it is executed.
+/// The token stays in escrow.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `queue_id` - the id of the queued release
+pub fn try_cancel_queued_release(
+    deps: DepsMut,
+    info: MessageInfo,
+    queue_id: u64,
+) -> Result<Response, ContractError> {
+    // Check if sender is a guardian or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_guardian(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let release = QUEUED_RELEASES
+        .may_load(deps.storage, queue_id.into())?
+        .ok_or(ContractError::QueuedReleaseNotFound { queue_id })?;
+    remove_queued_release(deps.storage, queue_id, &release);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_queued_release")
+        .add_attribute("queue_id", queue_id.to_string())
+        .add_attribute("terra_collection", release.collection)
+        .add_attribute("token_id", release.token_id))
+}
+
+/// Allows operators to make an escrowed NFT claimable by a Terra address.
+/// The recipient pulls the NFT with `ClaimNft` instead of the bridge pushing it.
+/// The token's unacknowledged inbound record is closed, so it can no longer be reclaimed.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `sn_coll_addr` - the SN collection's address
+/// * `sn_sender` - the SN address that bridged the NFT
+/// * `recipient` - the Terra address allowed to claim the NFT
+/// * `token_id` - id of the token being bridged
+/// * `expires_in` - optional number of seconds after which the claim can no longer be made
+#[allow(clippy::too_many_arguments)]
+pub fn try_mark_claimable(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sn_coll_addr: String,
+    sn_sender: String,
+    recipient: String,
+    token_id: String,
+    expires_in: Option<u64>,
+) -> Result<Response, ContractError> {
+    // Check if sender is an operator or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let recipient_valid = deps.api.addr_validate(&recipient)?;
+    check_not_denied(deps.as_ref(), &[recipient_valid.as_str(), &sn_sender])?;
+    let terra_collection = SN_TO_TERRA_MAP.load(deps.storage, sn_coll_addr.to_owned())?;
+
+    // Check if the bridge is paused
+    let is_paused = check_is_paused(deps.storage, terra_collection.to_owned())?;
+    if is_paused {
+        return Err(ContractError::BridgePaused {});
+    }
+
+    // Claims would bypass the time-lock queue
+    if release_delay(deps.storage, &terra_collection)?.is_some() {
+        return Err(ContractError::ReleaseTimeLocked {
+            collection: terra_collection.into_string(),
+        });
+    }
+
+    check_not_frozen(deps.storage, &terra_collection, &token_id)?;
+    check_not_queued(deps.storage, &terra_collection, &token_id)?;
+    check_in_custody(&deps, &env, &terra_collection, &token_id)?;
+
+    check_not_claimable(deps.storage, &env, &terra_collection, &token_id)?;
+    remove_expired_claim(deps.storage, &terra_collection, &token_id)?;
+
+    // The token is on its way back from SN, so its deposit can no longer be reclaimed
+    close_pending_bridge(
+        deps.storage,
+        &terra_collection,
+        &token_id,
+        BridgeStatus::Completed,
+    )?;
+
+    let key = (terra_collection.to_owned(), token_id.to_owned());
+    let claim = Claim {
+        recipient: recipient_valid.to_owned(),
+        sn_collection: sn_coll_addr.to_owned(),
+        sn_address: sn_sender.to_owned(),
+        expires_at: expires_in.map(|secs| env.block.time.seconds() + secs),
+    };
+    CLAIMS.save(deps.storage, key.clone(), &claim)?;
+    CLAIMS_BY_RECIPIENT.save(
+        deps.storage,
+        (recipient_valid, key.0.clone(), key.1.clone()),
+        &key,
+    )?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "mark_claimable")
+        .add_attribute("secret_sender", sn_sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("terra_collection", terra_collection)
+        .add_attribute("secret_collection", sn_coll_addr)
+        .add_attribute("token_id", token_id);
+    if let Some(expires_at) = claim.expires_at {
+        response = response.add_attribute("expires_at", expires_at.to_string());
+    }
+    Ok(response)
+}
+
+/// Allows a recipient to claim an NFT that was made claimable by an operator.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `collection` - the Terra collection's address
+/// * `token_id` - id of the token being claimed
+pub fn try_claim_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let terra_collection = deps.api.addr_validate(&collection)?;
+    let key = (terra_collection.to_owned(), token_id.to_owned());
+    let claim =
+        CLAIMS
+            .may_load(deps.storage, key.clone())?
+            .ok_or(ContractError::ClaimNotFound {
+                token_id: token_id.to_owned(),
+            })?;
+
+    // Only the recipient can claim the NFT, and only before the claim expires
+    if claim.recipient != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    check_not_denied(deps.as_ref(), &[info.sender.as_str(), &claim.sn_address])?;
+    if let Some(expires_at) = claim.expires_at {
+        if env.block.time.seconds() >= expires_at {
+            return Err(ContractError::ClaimExpired { token_id });
+        }
+    }
+
+    // Check if the bridge is paused
+    let is_paused = check_is_paused(deps.storage, terra_collection.to_owned())?;
+    if is_paused {
+        return Err(ContractError::BridgePaused {});
+    }
+
+    check_not_frozen(deps.storage, &terra_collection, &token_id)?;
+    check_in_custody(&deps, &env, &terra_collection, &token_id)?;
+    check_rate_limit(
+        deps.storage,
+        &env,
+        RateScope::Collection,
+        terra_collection.as_str(),
+    )?;
+    record_rate_limit(
+        deps.storage,
+        &env,
+        RateScope::Collection,
+        terra_collection.as_str(),
+    )?;
+    remove_claim(deps.storage, key, &claim);
+
+    let (pending, send) = dispatch_release(
+        deps.storage,
+        &env,
+        terra_collection.to_owned(),
+        claim.sn_collection.to_owned(),
+        claim.sn_address.to_owned(),
+        claim.recipient.to_owned(),
+        token_id.to_owned(),
+        DeliveryMode::Claim,
+        None,
+        None,
+    )?;
+    let history_id = pending.history_id;
+
+    let reply_id = save_pending_release(deps.storage, &pending)?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_always(send, reply_id))
+        .add_attribute("action", "claim_nft")
+        .add_attribute("secret_sender", claim.sn_address)
+        .add_attribute("recipient", claim.recipient)
+        .add_attribute("terra_collection", terra_collection)
+        .add_attribute("secret_collection", claim.sn_collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("history_id", history_id.to_string())
+        .add_attributes(history_chain_attributes(deps.storage)?))
+}
+
+/// Allows admins to revoke an unclaimed NFT's claim. The NFT remains in escrow
+/// and can be released again.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `collection` - the Terra collection's address
+/// * `token_id` - id of the claimable token
+pub fn try_revoke_claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let terra_collection = deps.api.addr_validate(&collection)?;
+    let key = (terra_collection.to_owned(), token_id.to_owned());
+    let claim =
+        CLAIMS
+            .may_load(deps.storage, key.clone())?
+            .ok_or(ContractError::ClaimNotFound {
+                token_id: token_id.to_owned(),
+            })?;
+    remove_claim(deps.storage, key, &claim);
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_claim")
+        .add_attribute("recipient", claim.recipient)
+        .add_attribute("terra_collection", terra_collection)
+        .add_attribute("token_id", token_id))
+}
+
+/// Fails with [ContractError::AddressDenied] if any of `addresses` is on the denylist
+fn check_not_denied(deps: Deps, addresses: &[&str]) -> Result<(), ContractError> {
+    for address in addresses {
+        if check_is_denied(deps, address)? {
+            return Err(ContractError::AddressDenied {
+                address: address.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Fails with [ContractError::RateLimitExceeded] if `subject` used up its limit for the current window
+fn check_rate_limit(
+    store: &dyn Storage,
+    env: &Env,
+    scope: RateScope,
+    subject: &str,
+) -> Result<(), ContractError> {
+    let limits = match RATE_LIMITS.may_load(store)? {
+        Some(limits) => limits,
+        None => return Ok(()),
+    };
+    if let Some(max) = scope.limit(&limits) {
+        let usage = window_usage(
+            store,
+            scope,
+            subject,
+            limits.window,
+            env.block.time.seconds(),
+        )?;
+        if usage.len() as u64 >= max as u64 {
+            return Err(ContractError::RateLimitExceeded {
+                scope: scope.as_str().to_owned(),
+                subject: subject.to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Records activity by `subject` in the current window
+fn record_rate_limit(
+    store: &mut dyn Storage,
+    env: &Env,
+    scope: RateScope,
+    subject: &str,
+) -> StdResult<()> {
+    let limits = match RATE_LIMITS.may_load(store)? {
+        Some(limits) if scope.limit(&limits).is_some() => limits,
+        _ => return Ok(()),
+    };
+    let now = env.block.time.seconds();
+    let mut usage = window_usage(store, scope, subject, limits.window, now)?;
+    usage.push(now);
+    RATE_USAGE.save(
+        store,
+        (scope.as_str().to_owned(), subject.to_owned()),
+        &usage,
+    )
+}
+
+/// Pauses the collection and fails with [ContractError::CircuitBreakerTripped] if releasing
+/// the token would meet one of the circuit breaker's trip conditions
+fn check_circuit_breaker(
+    store: &mut dyn Storage,
+    env: &Env,
+    terra_collection: &Addr,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    let circuit_breaker = match CIRCUIT_BREAKER.may_load(store)? {
+        Some(circuit_breaker) => circuit_breaker,
+        None => return Ok(()),
+    };
+
+    // Tokens received before the escrow ledger was tracked are escrowed if their latest record
+    // is inbound, until they are backfilled
+    let is_escrowed = ESCROW.has(store, (terra_collection.to_owned(), token_id.to_owned()))
+        || matches!(
+            latest_history(store, terra_collection.to_owned(), token_id.to_owned())?,
+            Some((_, record)) if record.is_enter
+        );
+    let reason = if circuit_breaker.trip_on_unescrowed_release && !is_escrowed {
+        Some(TripReason::UnescrowedRelease {
+            token_id: token_id.to_owned(),
+        })
+    } else {
+        let usage = breaker_usage(store, env, &circuit_breaker, terra_collection)?;
+        circuit_breaker
+            .max_releases_per_window
+            .filter(|max| usage.len() as u64 >= *max as u64)
+            .map(|max| TripReason::ReleaseThreshold {
+                max,
+                window: circuit_breaker.window,
+            })
+    };
+
+    match reason {
+        Some(reason) => {
+            let (scope, reason) = trip_circuit_breaker(store, env, Some(terra_collection), reason)?;
+            Err(ContractError::CircuitBreakerTripped { scope, reason })
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns the times of the collection's releases within the circuit breaker's window
+fn breaker_usage(
+    store: &dyn Storage,
+    env: &Env,
+    circuit_breaker: &CircuitBreaker,
+    terra_collection: &Addr,
+) -> StdResult<Vec<u64>> {
+    let now = env.block.time.seconds();
+    let mut usage = BREAKER_USAGE
+        .may_load(store, terra_collection.to_owned())?
+        .unwrap_or_default();
+    usage.retain(|time| time + circuit_breaker.window > now);
+    Ok(usage)
+}
+
+/// Records a release from the collection in the circuit breaker's current window
+fn record_breaker_usage(
+    store: &mut dyn Storage,
+    env: &Env,
+    terra_collection: &Addr,
+) -> StdResult<()> {
+    let circuit_breaker = match CIRCUIT_BREAKER.may_load(store)? {
+        Some(circuit_breaker) if circuit_breaker.max_releases_per_window.is_some() => {
+            circuit_breaker
+        }
+        _ => return Ok(()),
+    };
+    let mut usage = breaker_usage(store, env, &circuit_breaker, terra_collection)?;
+    usage.push(env.block.time.seconds());
+    BREAKER_USAGE.save(store, terra_collection.to_owned(), &usage)
+}
+
+/// Pauses the collection, or the whole bridge when `terra_collection` is `None`, and saves why.
+/// Returns the scope that was paused and the reason it was paused
+fn trip_circuit_breaker(
+    store: &mut dyn Storage,
+    env: &Env,
+    terra_collection: Option<&Addr>,
+    reason: TripReason,
+) -> StdResult<(String, String)> {
+    let trip = Trip {
+        reason: reason.to_owned(),
+        tripped_at: env.block.time.seconds(),
+    };
+    let scope = match terra_collection {
+        Some(coll) => {
+            set_paused(store, Some(coll), true)?;
+            COLL_TRIPS.save(store, coll.to_owned(), &trip)?;
+            coll.to_string()
+        }
+        None => {
+            set_paused(store, None, true)?;
+            BRIDGE_TRIP.save(store, &trip)?;
+            "bridge".to_owned()
+        }
+    };
+    Ok((scope, reason.as_str().to_owned()))
+}
+
+/// The event emitted when the circuit breaker pauses the bridge or a collection
+fn tripped_event(scope: &str, reason: &str) -> Event {
+    Event::new("circuit_breaker_tripped")
+        .add_attribute("scope", scope)
+        .add_attribute("reason", reason)
+}
+
+/// Returns how long releases from the collection are queued for, if they are queued.
+/// This is the longer of the collection's time-lock and the challenge period
+fn release_delay(store: &dyn Storage, terra_collection: &Addr) -> StdResult<Option<u64>> {
+    let delay = RELEASE_DELAYS.may_load(store, terra_collection.to_owned())?;
+    let period = CHALLENGE_PERIOD.may_load(store)?;
+    Ok(delay.max(period))
+}
+
+/// Fails with [ContractError::TokenQueued] if the token is waiting in the time-lock queue
+fn check_not_queued(
+    store: &dyn Storage,
+    terra_collection: &Addr,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    if QUEUED_TOKENS.has(store, (terra_collection.to_owned(), token_id.to_owned())) {
+        return Err(ContractError::TokenQueued {
+            token_id: token_id.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Fails with [ContractError::TokenFrozen] if an admin froze the token
+fn check_not_frozen(
+    store: &dyn Storage,
+    terra_collection: &Addr,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    if FROZEN_TOKENS.has(store, (terra_collection.to_owned(), token_id.to_owned())) {
+        return Err(ContractError::TokenFrozen {
+            token_id: token_id.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Closes the token's most recent inbound BridgeRecord with `status` if it still awaits
+/// acknowledgement
+fn close_pending_bridge(
+    store: &mut dyn Storage,
+    terra_collection: &Addr,
+    token_id: &str,
+    status: BridgeStatus,
+) -> StdResult<()> {
+    let latest = latest_inbound_history(store, terra_collection.to_owned(), token_id.to_owned())?;
+    if let Some((history_id, mut record)) = latest {
+        let pending_seq = record
+            .seq
+            .filter(|_| record.status == Some(BridgeStatus::Pending));
+        if let Some(seq) = pending_seq {
+            PENDING_BRIDGES.remove(store, seq.into());
+            RECLAIM_HOLDS.remove(store, seq.into());
+            record.status = Some(status);
+            write_history(store, terra_collection, token_id, history_id, &record)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fails with [ContractError::TokenNotInCustody] unless the bridge owns the token
+fn check_in_custody(
+    deps: &DepsMut,
+    env: &Env,
+    terra_collection: &Addr,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    let owner = query_owner_of(
+        &deps.querier,
+        terra_collection.to_owned(),
+        token_id.to_owned(),
+    )
+    .ok();
+    if owner.as_deref() != Some(env.contract.address.as_str()) {
+        return Err(ContractError::TokenNotInCustody {
+            token_id: token_id.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Fails with [ContractError::TokenClaimable] if the token has a claim that has not expired
+fn check_not_claimable(
+    store: &dyn Storage,
+    env: &Env,
+    terra_collection: &Addr,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    let claim = CLAIMS.may_load(store, (terra_collection.to_owned(), token_id.to_owned()))?;
+    let is_live = claim.map_or(false, |claim| {
+        claim
+            .expires_at
+            .map_or(true, |expires_at| env.block.time.seconds() < expires_at)
+    });
+    if is_live {
+        return Err(ContractError::TokenClaimable {
+            token_id: token_id.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Removes the token's claim. Only called after [check_not_claimable], so the claim has expired
+fn remove_expired_claim(
+    store: &mut dyn Storage,
+    terra_collection: &Addr,
+    token_id: &str,
+) -> StdResult<()> {
+    let key = (terra_collection.to_owned(), token_id.to_owned());
+    if let Some(claim) = CLAIMS.may_load(store, key.clone())? {
+        remove_claim(store, key, &claim);
+    }
+    Ok(())
+}
+
+fn remove_claim(store: &mut dyn Storage, key: EscrowPK, claim: &Claim) {
+    CLAIMS_BY_RECIPIENT.remove(
+        store,
+        (claim.recipient.to_owned(), key.0.clone(), key.1.clone()),
+    );
+    CLAIMS.remove(store, key);
+}
+
+/// Saves the history for a token leaving escrow and creates the message that delivers it.
+/// The token is removed from the escrow ledger, and the returned [PendingRelease] holds
+/// what is needed to put it back if the transfer fails.
+#[allow(clippy::too_many_arguments)]
+fn dispatch_release(
+    store: &mut dyn Storage,
+    env: &Env,
+    terra_collection: Addr,
+    sn_coll_addr: String,
+    sn_sender: String,
+    recipient: Addr,
+    token_id: String,
+    delivery_mode: DeliveryMode,
+    payload: Option<Binary>,
+    payload_hash: Option<Binary>,
+) -> Result<(PendingRelease, WasmMsg), ContractError> {
+    // The token made it back from SN, so its inbound record can no longer be reclaimed
+    close_pending_bridge(store, &terra_collection, &token_id, BridgeStatus::Completed)?;
+
+    // Create & save history. A failed release's record is replaced by the release superseding it
+    let failed =
+        FAILED_RELEASES.may_load(store, (terra_collection.to_owned(), token_id.to_owned()))?;
+    let record = BridgeRecord {
+        is_enter: false,
+        token_id: token_id.to_owned(),
+        source_address: Some(recipient.to_owned()),
+        source_collection: terra_collection.to_owned(),
+        destination_address: Some(sn_sender.to_owned()),
+        destination_collection: sn_coll_addr.to_owned(),
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+        payload_hash,
+        delivery_mode: Some(delivery_mode.to_owned()),
+        seq: None,
+        status: Some(BridgeStatus::Completed),
+        sn_tx_hash: None,
+    };
+    let history_id = match failed {
+        Some(failed) => {
+            write_history(
+                store,
+                &terra_collection,
+                &token_id,
+                failed.history_id,
+                &record,
+            )?;
+            failed.history_id
+        }
+        None => save_history(
+            store,
+            terra_collection.to_owned(),
+            token_id.to_owned(),
+            record,
+        )?,
+    };
+
+    // Remove the token from the escrow ledger and clear any earlier failed release or expired claim
+    let escrow = remove_escrow(store, terra_collection.to_owned(), token_id.to_owned())?;
+    remove_expired_claim(store, &terra_collection, &token_id)?;
+    FAILED_RELEASES.remove(store, (terra_collection.to_owned(), token_id.to_owned()));
+
+    // Create the message to transfer ownership
+    let msg = match delivery_mode {
+        DeliveryMode::Send => SendNft {
+            contract: recipient.to_string(),
+            token_id: token_id.to_owned(),
+            msg: payload.unwrap_or_default(),
+        },
+        DeliveryMode::Transfer | DeliveryMode::Claim => TransferNft {
+            recipient: recipient.to_string(),
+            token_id: token_id.to_owned(),
+        },
+    };
+    let send = WasmMsg::Execute {
+        contract_addr: terra_collection.to_string(),
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    };
+
+    let pending = PendingRelease {
+        collection: terra_collection,
+        token_id,
+        recipient,
+        sn_collection: sn_coll_addr,
+        sn_address: sn_sender,
+        history_id,
+        escrow,
+    };
+    Ok((pending, send))
+}
+
+/// Allows operators to release NFTs from bridge escrow.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `sender` - the Terra address bridging the NFT (from Cw721ReceiveMsg)
+/// * `token_id` - id of the token being bridged
+pub fn try_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    // A wound down bridge never receives tokens again
+    if WOUND_DOWN_AT.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::BridgeWoundDown {});
+    }
+
+    // Check if the bridge is paused
+    let is_paused = check_is_paused(deps.storage, info.sender.to_owned())?;
+    if is_paused {
+        return Err(ContractError::BridgePaused {});
+    }
+
+    // Don't accept tokens while no relayer is around to bridge them
+    if !check_is_live(deps.storage, &env)? {
+        return Err(ContractError::RelayerOffline {});
+    }
+
+    // Validate NFT sender
+    let sender_addr = deps.api.addr_validate(&sender)?;
+
+    // Check whitelist to see if the collection is mapped to Secret
+    let sn_coll_addr = TERRA_TO_SN_MAP
+        .may_load(deps.storage, info.sender.to_owned())?
+        .ok_or(ContractError::UnauthorizedCollection {})?;
+    let sn_sender: String = from_binary(&msg)?;
+    check_not_denied(deps.as_ref(), &[sender_addr.as_str(), &sn_sender])?;
+
+    // Collections in allowlist-only mode only accept tokens from allowlisted senders
+    let allowlist_only = ALLOWLIST_ONLY
+        .may_load(deps.storage, info.sender.to_owned())?
+        .unwrap_or(false);
+    if allowlist_only
+        && !ALLOWLIST.has(
+            deps.storage,
+            (info.sender.to_owned(), sender_addr.to_owned()),
+        )
+    {
+        return Err(ContractError::NotAllowlisted { address: sender });
+    }
+    check_not_frozen(deps.storage, &info.sender, &token_id)?;
+
+    check_rate_limit(deps.storage, &env, RateScope::User, sender_addr.as_str())?;
+    record_rate_limit(deps.storage, &env, RateScope::User, sender_addr.as_str())?;
+
+    // Fetch the token's metadata so it can be re-created on SN
+    let (nft_info, token_uri) =
+        query_nft_info(&deps.querier, info.sender.to_owned(), token_id.to_owned())?;
+    let metadata_hash = sha_256(nft_info.as_slice());
+
+    // Save history
+    let seq = next_bridge_seq(deps.storage)?;
+    let record = BridgeRecord {
+        token_id: token_id.to_owned(),
+        is_enter: true,
         source_address: Some(sender_addr.to_owned()),
         source_collection: info.sender.to_owned(),
         destination_address: Some(sn_sender.to_owned()),
         destination_collection: sn_coll_addr.to_owned(),
         block_height: env.block.height,
         block_time: env.block.time.seconds(),
+        payload_hash: None,
+        delivery_mode: None,
+        seq: Some(seq),
+        status: Some(BridgeStatus::Pending),
+        sn_tx_hash: None,
+    };
+
+    // Load next primary key and save history to storage
+    let hist_id = save_history(
+        deps.storage,
+        info.sender.to_owned(),
+        token_id.to_owned(),
+        record,
+    )?;
+    PENDING_BRIDGES.save(
+        deps.storage,
+        seq.into(),
+        &(info.sender.to_owned(), token_id.to_owned(), hist_id),
+    )?;
+
+    // Record the token in the escrow ledger
+    let escrow = EscrowRecord {
+        depositor: sender_addr.to_owned(),
+        destination_address: sn_sender.to_owned(),
+        block_height: env.block.height,
+    };
+    save_escrow(
+        deps.storage,
+        info.sender.to_owned(),
+        token_id.to_owned(),
+        escrow,
+    )?;
+
+    // Snapshot the metadata that was bridged alongside the token
+    let snapshot = MetadataSnapshot {
+        nft_info: nft_info.to_owned(),
+        hash: metadata_hash.to_owned(),
+        history_id: hist_id,
+    };
+    METADATA.save(deps.storage, (info.sender.to_owned(), token_id), &snapshot)?;
+
+    let mut response = Response::default()
+        .set_data(nft_info)
+        .add_attribute("action", "receive_nft")
+        .add_attribute("terra_sender", sender_addr)
+        .add_attribute("secret_sender", sn_sender)
+        .add_attribute("terra_collection_addr", info.sender)
+        .add_attribute("secret_collection_addr", sn_coll_addr)
+        .add_attribute("history_id", hist_id.to_string())
+        .add_attributes(history_chain_attributes(deps.storage)?)
+        .add_attribute("seq", seq.to_string())
+        .add_attribute("metadata_hash", metadata_hash.to_base64());
+    if let Some(uri) = token_uri {
+        response = response.add_attribute("token_uri", uri);
+    }
+    Ok(response)
+}
+
+/// Allows operators to retry a failed release, optionally redirecting the NFT to a new recipient.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `collection` - the Terra collection's address
+/// * `token_id` - id of the token that failed to release
+/// * `recipient` - the Terra address receiving the NFT. Defaults to the original recipient
+/// * `recipient_is_contract` - overrides whether the NFT is delivered with `SendNft`
+/// * `payload` - optional message forwarded to a contract recipient
+#[allow(clippy::too_many_arguments)]
+pub fn try_retry_release(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+    recipient: Option<String>,
+    recipient_is_contract: Option<bool>,
+    payload: Option<Binary>,
+) -> Result<Response, ContractError> {
+    // Check if sender is an operator or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let coll_addr = deps.api.addr_validate(&collection)?;
+    let failed = FAILED_RELEASES
+        .may_load(deps.storage, (coll_addr, token_id.to_owned()))?
+        .ok_or(ContractError::FailedReleaseNotFound {
+            token_id: token_id.to_owned(),
+        })?;
+
+    try_release_nft(
+        deps,
+        env,
+        info,
+        failed.sn_collection,
+        failed.sn_address,
+        recipient.unwrap_or_else(|| failed.recipient.into_string()),
+        token_id,
+        recipient_is_contract,
+        payload,
+        None,
+    )
+}
+
+/// Clears a release whose transfer succeeded. Called from the `reply` entry point.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `reply_id` - the id of the SubMsg
+pub fn try_record_release(deps: DepsMut, reply_id: u64) -> Result<Response, ContractError> {
+    take_pending_release(deps.storage, reply_id)?;
+    Ok(Response::default())
+}
+
+/// Loads and removes the PendingRelease a reply refers to
+fn take_pending_release(store: &mut dyn Storage, reply_id: u64) -> StdResult<PendingRelease> {
+    let pending = PENDING_RELEASES.load(store, reply_id.into())?;
+    PENDING_RELEASES.remove(store, reply_id.into());
+    Ok(pending)
+}
+
+/// Records a release whose transfer failed and returns the token to escrow.
+/// Called from the `reply` entry point.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `reply_id` - the id of the failed SubMsg
+/// * `error` - the error returned by the Terra collection
+pub fn try_record_failed_release(
+    deps: DepsMut,
+    env: Env,
+    reply_id: u64,
+    error: String,
+) -> Result<Response, ContractError> {
+    let pending = take_pending_release(deps.storage, reply_id)?;
+
+    // Put the token back in escrow
+    if let Some(escrow) = pending.escrow {
+        save_escrow(
+            deps.storage,
+            pending.collection.to_owned(),
+            pending.token_id.to_owned(),
+            escrow,
+        )?;
+    }
+
+    let mut record = HISTORY.load(
+        deps.storage,
+        (
+            pending.collection.to_owned(),
+            pending.token_id.to_owned(),
+            pending.history_id.into(),
+        ),
+    )?;
+    record.status = Some(BridgeStatus::Failed);
+    write_history(
+        deps.storage,
+        &pending.collection,
+        &pending.token_id,
+        pending.history_id,
+        &record,
+    )?;
+
+    let failed = FailedRelease {
+        recipient: pending.recipient,
+        sn_collection: pending.sn_collection,
+        sn_address: pending.sn_address,
+        history_id: pending.history_id,
+        error: error.to_owned(),
+        block_height: env.block.height,
+    };
+    FAILED_RELEASES.save(
+        deps.storage,
+        (pending.collection.to_owned(), pending.token_id.to_owned()),
+        &failed,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "release_failed")
+        .add_attribute("terra_collection", pending.collection)
+        .add_attribute("token_id", pending.token_id)
+        .add_attribute("history_id", pending.history_id.to_string())
+        .add_attributes(history_chain_attributes(deps.storage)?)
+        .add_attribute("error", error))
+}
+
+/// Allows operators to acknowledge whether SN accepted a received token.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `seq` - the sequence number of the received token
+/// * `sn_tx_hash` - optional hash of the SN transaction that handled the token
+/// * `success` - `true` if SN accepted the token
+pub fn try_acknowledge_bridge(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    seq: u64,
+    sn_tx_hash: Option<String>,
+    success: bool,
+) -> Result<Response, ContractError> {
+    // Check if sender is an operator or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let acknowledgement = Acknowledgement {
+        success,
+        sn_tx_hash: sn_tx_hash.to_owned(),
+    };
+
+    // A different outcome for an acknowledged record means SN attestations disagree
+    let pending = PENDING_BRIDGES.may_load(deps.storage, seq.into())?;
+    let trips = CIRCUIT_BREAKER
+        .may_load(deps.storage)?
+        .map_or(false, |cb| cb.trip_on_conflicting_acknowledgement);
+    if pending.is_none() && trips {
+        let earlier = ACKNOWLEDGEMENTS.may_load(deps.storage, seq.into())?;
+        if earlier.map_or(false, |earlier| earlier.conflicts_with(&acknowledgement)) {
+            let reason = TripReason::ConflictingAcknowledgement { seq };
+            let (scope, reason) = trip_circuit_breaker(deps.storage, &env, None, reason)?;
+            return Ok(Response::new()
+                .add_event(tripped_event(&scope, &reason))
+                .add_attribute("action", "acknowledge_bridge")
+                .add_attribute("seq", seq.to_string()));
+        }
+    }
+
+    // Only pending records can be acknowledged
+    let (collection, token_id, history_id) =
+        pending.ok_or(ContractError::BridgeNotPending { seq })?;
+    PENDING_BRIDGES.remove(deps.storage, seq.into());
+    ACKNOWLEDGEMENTS.save(deps.storage, seq.into(), &acknowledgement)?;
+
+    let status = if success {
+        BridgeStatus::Completed
+    } else {
+        BridgeStatus::Failed
+    };
+    let mut record = HISTORY.load(
+        deps.storage,
+        (
+            collection.to_owned(),
+            token_id.to_owned(),
+            history_id.into(),
+        ),
+    )?;
+    record.status = Some(status);
+    record.sn_tx_hash = sn_tx_hash.to_owned();
+    write_history(deps.storage, &collection, &token_id, history_id, &record)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "acknowledge_bridge")
+        .add_attribute("seq", seq.to_string())
+        .add_attribute("terra_collection", collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("history_id", history_id.to_string())
+        .add_attributes(history_chain_attributes(deps.storage)?)
+        .add_attribute("success", success.to_string());
+    if let Some(hash) = sn_tx_hash {
+        response = response.add_attribute("sn_tx_hash", hash);
+    }
+    Ok(response)
+}
+
+/// Allows operators to record the last SN event their relayer fully processed.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `checkpoint` - the SN position. Can not be before the sender's current checkpoint
+pub fn try_checkpoint(
+    deps: DepsMut,
+    info: MessageInfo,
+    checkpoint: Checkpoint,
+) -> Result<Response, ContractError> {
+    // Check if sender is an operator or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let response = Response::new()
+        .add_attribute("action", "checkpoint")
+        .add_attribute("operator", info.sender.to_owned());
+    apply_checkpoint(deps.storage, &info.sender, Some(checkpoint), response)
+}
+
+/// Advances the operator's checkpoint, and the global checkpoint if it is the furthest yet.
+/// Fails with [ContractError::CheckpointRegression] if the operator's checkpoint would move back
+fn apply_checkpoint(
+    store: &mut dyn Storage,
+    operator: &Addr,
+    checkpoint: Option<Checkpoint>,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let checkpoint = match checkpoint {
+        Some(checkpoint) => checkpoint,
+        None => return Ok(response),
+    };
+    if let Some(last) = CHECKPOINTS.may_load(store, operator.to_owned())? {
+        if checkpoint < last {
+            return Err(ContractError::CheckpointRegression {
+                sn_height: last.sn_height,
+                event_index: last.event_index,
+            });
+        }
+    }
+    CHECKPOINTS.save(store, operator.to_owned(), &checkpoint)?;
+    if CHECKPOINT
+        .may_load(store)?
+        .map_or(true, |global| checkpoint > global)
+    {
+        CHECKPOINT.save(store, &checkpoint)?;
+    }
+
+    Ok(response
+        .add_attribute("sn_height", checkpoint.sn_height.to_string())
+        .add_attribute("event_index", checkpoint.event_index.to_string()))
+}
+
+/// Allows operators to signal that their relayer is alive.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `last_sn_height` - the last SN block height the relayer has seen
+pub fn try_heartbeat(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    last_sn_height: u64,
+) -> Result<Response, ContractError> {
+    // Check if sender is an operator or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let heartbeat = Heartbeat {
+        last_sn_height,
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+    };
+    HEARTBEATS.save(deps.storage, info.sender.to_owned(), &heartbeat)?;
+    LAST_HEARTBEAT.save(deps.storage, &heartbeat.block_time)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "heartbeat")
+        .add_attribute("operator", info.sender)
+        .add_attribute("last_sn_height", last_sn_height.to_string()))
+}
+
+/// Allows operators to delay or block reclaiming a received token while SN is still processing it.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `info` - additional information about the message sender and attached funds
+/// * `seq` - the sequence number of the received token
+/// * `until` - the time (in seconds since 01/01/1970) before which the token can not be reclaimed.
+///   `None` blocks reclaiming until the token is acknowledged
+pub fn try_hold_reclaim(
+    deps: DepsMut,
+    info: MessageInfo,
+    seq: u64,
+    until: Option<u64>,
+) -> Result<Response, ContractError> {
+    // Check if sender is an operator or admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_operator(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !PENDING_BRIDGES.has(deps.storage, seq.into()) {
+        return Err(ContractError::BridgeNotPending { seq });
+    }
+    RECLAIM_HOLDS.save(deps.storage, seq.into(), &ReclaimHold { until })?;
+
+    let until = until.map_or_else(|| "blocked".to_string(), |until| until.to_string());
+    Ok(Response::new()
+        .add_attribute("action", "hold_reclaim")
+        .add_attribute("seq", seq.to_string())
+        .add_attribute("until", until))
+}
+
+/// Allows a depositor to take back a received token that SN never acknowledged.
+/// The token can be reclaimed once the reclaim timeout has passed, unless an operator holds it.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `seq` - the sequence number of the received token
+pub fn try_reclaim_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    seq: u64,
+) -> Result<Response, ContractError> {
+    let (collection, token_id, history_id) = PENDING_BRIDGES
+        .may_load(deps.storage, seq.into())?
+        .ok_or(ContractError::BridgeNotPending { seq })?;
+    let key = (
+        collection.to_owned(),
+        token_id.to_owned(),
+        history_id.into(),
+    );
+    let mut record = HISTORY.load(deps.storage, key)?;
+
+    // Only the depositor can reclaim the token, and only the deposit it is still escrowed for
+    if record.source_address.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let latest = latest_inbound_history(deps.storage, collection.to_owned(), token_id.to_owned())?;
+    if latest.map(|(latest_id, _)| latest_id) != Some(history_id) {
+        return Err(ContractError::BridgeNotPending { seq });
+    }
+    let escrow = ESCROW.may_load(deps.storage, (collection.to_owned(), token_id.to_owned()))?;
+    if escrow.map(|escrow| escrow.depositor) != Some(info.sender.to_owned()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    check_not_denied(deps.as_ref(), &[info.sender.as_str()])?;
+
+    let available_at =
+        reclaimable_at(deps.storage, seq, &record)?.ok_or(ContractError::ReclaimBlocked { seq })?;
+    if env.block.time.seconds() < available_at {
+        return Err(ContractError::ReclaimNotReady { seq, available_at });
+    }
+
+    // Check if the bridge is paused
+    let is_paused = check_is_paused(deps.storage, collection.to_owned())?;
+    if is_paused {
+        return Err(ContractError::BridgePaused {});
+    }
+
+    check_not_frozen(deps.storage, &collection, &token_id)?;
+    check_not_queued(deps.storage, &collection, &token_id)?;
+    check_in_custody(&deps, &env, &collection, &token_id)?;
+    check_not_claimable(deps.storage, &env, &collection, &token_id)?;
+
+    // The token leaves the bridge, so clear any expired claim or failed release
+    remove_expired_claim(deps.storage, &collection, &token_id)?;
+    FAILED_RELEASES.remove(deps.storage, (collection.to_owned(), token_id.to_owned()));
+
+    // Mark the inbound record as refunded
+    PENDING_BRIDGES.remove(deps.storage, seq.into());
+    RECLAIM_HOLDS.remove(deps.storage, seq.into());
+    record.status = Some(BridgeStatus::Refunded);
+    write_history(deps.storage, &collection, &token_id, history_id, &record)?;
+
+    // Create & save history for the refund
+    let refund = BridgeRecord {
+        is_enter: false,
+        token_id: token_id.to_owned(),
+        source_address: Some(info.sender.to_owned()),
+        source_collection: collection.to_owned(),
+        destination_address: record.destination_address,
+        destination_collection: record.destination_collection,
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+        payload_hash: None,
+        delivery_mode: Some(DeliveryMode::Transfer),
+        seq: None,
+        status: Some(BridgeStatus::Refunded),
+        sn_tx_hash: None,
+    };
+    let refund_id = save_history(
+        deps.storage,
+        collection.to_owned(),
+        token_id.to_owned(),
+        refund,
+    )?;
+    remove_escrow(deps.storage, collection.to_owned(), token_id.to_owned())?;
+
+    let send = WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_binary(&TransferNft {
+            recipient: info.sender.to_string(),
+            token_id: token_id.to_owned(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(send)
+        .add_attribute("action", "reclaim_nft")
+        .add_attribute("seq", seq.to_string())
+        .add_attribute("recipient", info.sender)
+        .add_attribute("terra_collection", collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("history_id", refund_id.to_string())
+        .add_attributes(history_chain_attributes(deps.storage)?))
+}
+
+/// Allows a depositor to withdraw a token they sent to the bridge once the bridge has been wound down.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `collection` - the Terra collection's address
+/// * `token_id` - id of the token being withdrawn
+pub fn try_withdraw_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    if WOUND_DOWN_AT.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::BridgeNotWoundDown {});
+    }
+
+    let terra_collection = deps.api.addr_validate(&collection)?;
+    let key = (terra_collection.to_owned(), token_id.to_owned());
+    let escrow =
+        ESCROW
+            .may_load(deps.storage, key.clone())?
+            .ok_or(ContractError::TokenNotInCustody {
+                token_id: token_id.to_owned(),
+            })?;
+
+    // Only the depositor can withdraw the token
+    if escrow.depositor != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    check_not_denied(deps.as_ref(), &[info.sender.as_str()])?;
+
+    // Pauses do not apply once the bridge is wound down, depositors must always get tokens back
+    check_not_frozen(deps.storage, &terra_collection, &token_id)?;
+    check_not_queued(deps.storage, &terra_collection, &token_id)?;
+    check_in_custody(&deps, &env, &terra_collection, &token_id)?;
+    check_not_claimable(deps.storage, &env, &terra_collection, &token_id)?;
+    remove_expired_claim(deps.storage, &terra_collection, &token_id)?;
+
+    // An unacknowledged inbound record will never be acknowledged now
+    close_pending_bridge(
+        deps.storage,
+        &terra_collection,
+        &token_id,
+        BridgeStatus::Refunded,
+    )?;
+
+    // Create & save history for the withdrawal
+    let withdrawal = BridgeRecord {
+        is_enter: false,
+        token_id: token_id.to_owned(),
+        source_address: Some(info.sender.to_owned()),
+        source_collection: terra_collection.to_owned(),
+        destination_address: Some(escrow.destination_address),
+        destination_collection: TERRA_TO_SN_MAP
+            .may_load(deps.storage, terra_collection.to_owned())?
+            .unwrap_or_default(),
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+        payload_hash: None,
+        delivery_mode: Some(DeliveryMode::Transfer),
+        seq: None,
+        status: Some(BridgeStatus::Refunded),
+        sn_tx_hash: None,
     };
+    let history_id = save_history(
+        deps.storage,
+        terra_collection.to_owned(),
+        token_id.to_owned(),
+        withdrawal,
+    )?;
+    remove_escrow(
+        deps.storage,
+        terra_collection.to_owned(),
+        token_id.to_owned(),
+    )?;
 
-    // Load next primary key and save history to storage
-    let hist_id = save_history(deps.storage, info.sender.to_owned(), token_id, record)?;
+    let send = WasmMsg::Execute {
+        contract_addr: terra_collection.to_string(),
+        msg: to_binary(&TransferNft {
+            recipient: info.sender.to_string(),
+            token_id: token_id.to_owned(),
+        })?,
+        funds: vec![],
+    };
 
-    Ok(Response::default()
-        .add_attribute("action", "receive_nft")
-        .add_attribute("terra_sender", sender_addr)
-        .add_attribute("secret_sender", sn_sender)
-        .add_attribute("terra_collection_addr", info.sender)
-        .add_attribute("secret_collection_addr", sn_coll_addr)
-        .add_attribute("history_id", hist_id.to_string()))
+    Ok(Response::new()
+        .add_message(send)
+        .add_attribute("action", "withdraw_nft")
+        .add_attribute("recipient", info.sender)
+        .add_attribute("terra_collection", terra_collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("history_id", history_id.to_string())
+        .add_attributes(history_chain_attributes(deps.storage)?))
+}
+
+/// Allows admins to rebuild the escrow ledger for tokens received before it was tracked.
+/// A token is restored if its latest BridgeRecord is inbound and the bridge still owns it.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `collection` - the Terra collection's address
+/// * `start_after` - the token_id of the last token checked in the previous backfill
+/// * `limit` - the maximum number of tokens to check
+pub fn try_backfill_escrow(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u8>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let terra_collection = deps.api.addr_validate(&collection)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::Exclusive(s.into_bytes()));
+    let token_ids = HISTORY_COUNT
+        .prefix(terra_collection.to_owned())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| String::from_utf8(key).map_err(StdError::from))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    let mut restored = 0u32;
+    for token_id in token_ids.iter() {
+        let key = (terra_collection.to_owned(), token_id.to_owned());
+        if ESCROW.has(deps.storage, key.clone()) {
+            continue;
+        }
+        let record = match latest_history(deps.storage, key.0, key.1)? {
+            Some((_, record)) if record.is_enter => record,
+            _ => continue,
+        };
+        let depositor = match record.source_address {
+            Some(depositor) => depositor,
+            None => continue,
+        };
+        if check_in_custody(&deps, &env, &terra_collection, token_id).is_err() {
+            continue;
+        }
+        let escrow = EscrowRecord {
+            depositor,
+            destination_address: record.destination_address.unwrap_or_default(),
+            block_height: record.block_height,
+        };
+        save_escrow(
+            deps.storage,
+            terra_collection.to_owned(),
+            token_id.to_owned(),
+            escrow,
+        )?;
+        restored += 1;
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "backfill_escrow")
+        .add_attribute("terra_collection", terra_collection)
+        .add_attribute("checked", token_ids.len().to_string())
+        .add_attribute("restored", restored.to_string());
+    if let Some(last) = token_ids.last() {
+        response = response.add_attribute("last_token_id", last);
+    }
+    Ok(response)
+}
+
+/// Allows admins to return an NFT that was transferred to the bridge without `SendNft`.
+/// Only tokens the bridge owns that are not in the escrow ledger can be rescued.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `collection` - the Terra collection's address
+/// * `token_id` - id of the token being rescued
+/// * `recipient` - the Terra address receiving the token
+pub fn try_rescue_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let terra_collection = deps.api.addr_validate(&collection)?;
+    let recipient_valid = deps.api.addr_validate(&recipient)?;
+
+    // Tokens that were bridged must leave through the bridge
+    if ESCROW.has(
+        deps.storage,
+        (terra_collection.to_owned(), token_id.to_owned()),
+    ) {
+        return Err(ContractError::TokenEscrowed { token_id });
+    }
+    // Bridged tokens that predate the escrow ledger must be backfilled instead
+    let latest = latest_history(
+        deps.storage,
+        terra_collection.to_owned(),
+        token_id.to_owned(),
+    )?;
+    if matches!(latest, Some((_, record)) if record.is_enter) {
+        return Err(ContractError::TokenEscrowed { token_id });
+    }
+    check_not_frozen(deps.storage, &terra_collection, &token_id)?;
+    check_not_queued(deps.storage, &terra_collection, &token_id)?;
+    check_in_custody(&deps, &env, &terra_collection, &token_id)?;
+
+    let rescue = RescueRecord {
+        collection: terra_collection.to_owned(),
+        token_id: token_id.to_owned(),
+        recipient: recipient_valid.to_owned(),
+        admin: info.sender.to_owned(),
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+    };
+    let rescue_id = save_rescue(deps.storage, rescue)?;
+
+    let send = WasmMsg::Execute {
+        contract_addr: terra_collection.to_string(),
+        msg: to_binary(&TransferNft {
+            recipient: recipient_valid.to_string(),
+            token_id: token_id.to_owned(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(send)
+        .add_attribute("action", "rescue_nft")
+        .add_attribute("admin", info.sender)
+        .add_attribute("recipient", recipient_valid)
+        .add_attribute("terra_collection", terra_collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("rescue_id", rescue_id.to_string()))
+}
+
+/// Allows admins to withdraw native coins held by the bridge.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `recipient` - the Terra address receiving the coins
+/// * `amount` - the coins to withdraw
+pub fn try_withdraw_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let recipient_valid = deps.api.addr_validate(&recipient)?;
+    if amount.is_empty() || amount.iter().any(|coin| coin.amount.is_zero()) {
+        return Err(ContractError::EmptyWithdrawal {});
+    }
+
+    // Bonded funds belong to the operators
+    let bonded = TOTAL_BONDED.may_load(deps.storage)?.unwrap_or_default();
+    if !bonded.is_zero() {
+        let config = BOND_CONFIG.load(deps.storage)?;
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_owned(), config.denom.to_owned())?
+            .amount;
+        let withdrawn = amount
+            .iter()
+            .filter(|coin| coin.denom == config.denom)
+            .map(|coin| coin.amount)
+            .sum::<Uint128>();
+        if balance
+            .checked_sub(withdrawn)
+            .map_or(true, |left| left < bonded)
+        {
+            return Err(ContractError::BondedFunds {});
+        }
+    }
+
+    let withdrawal = WithdrawalRecord {
+        funds: WithdrawnFunds::Native(amount.to_owned()),
+        recipient: recipient_valid.to_owned(),
+        admin: info.sender.to_owned(),
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+    };
+    let withdrawal_id = save_withdrawal(deps.storage, withdrawal)?;
+
+    let send = BankMsg::Send {
+        to_address: recipient_valid.to_string(),
+        amount,
+    };
+
+    Ok(Response::new()
+        .add_message(send)
+        .add_attribute("action", "withdraw_native")
+        .add_attribute("admin", info.sender)
+        .add_attribute("recipient", recipient_valid)
+        .add_attribute("withdrawal_id", withdrawal_id.to_string()))
+}
+
+/// Allows admins to withdraw cw20 tokens held by the bridge.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `token` - the cw20 contract's address
+/// * `recipient` - the Terra address receiving the tokens
+/// * `amount` - the amount of tokens to withdraw
+pub fn try_withdraw_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token_valid = deps.api.addr_validate(&token)?;
+    let recipient_valid = deps.api.addr_validate(&recipient)?;
+    if amount.is_zero() {
+        return Err(ContractError::EmptyWithdrawal {});
+    }
+
+    let withdrawal = WithdrawalRecord {
+        funds: WithdrawnFunds::Cw20 {
+            token: token_valid.to_owned(),
+            amount,
+        },
+        recipient: recipient_valid.to_owned(),
+        admin: info.sender.to_owned(),
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+    };
+    let withdrawal_id = save_withdrawal(deps.storage, withdrawal)?;
+
+    let send = WasmMsg::Execute {
+        contract_addr: token_valid.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient_valid.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(send)
+        .add_attribute("action", "withdraw_cw20")
+        .add_attribute("admin", info.sender)
+        .add_attribute("recipient", recipient_valid)
+        .add_attribute("token", token_valid)
+        .add_attribute("amount", amount)
+        .add_attribute("withdrawal_id", withdrawal_id.to_string()))
+}
+
+/// Allows admins to freeze a token so it can not be received or released while a dispute is resolved.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `collection` - the Terra collection's address
+/// * `token_id` - id of the token being frozen
+/// * `note` - a note describing the dispute
+pub fn try_freeze_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+    note: String,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let terra_collection = deps.api.addr_validate(&collection)?;
+    let frozen = FrozenToken {
+        note,
+        admin: info.sender.to_owned(),
+        frozen_at: env.block.time.seconds(),
+    };
+    FROZEN_TOKENS.save(
+        deps.storage,
+        (terra_collection.to_owned(), token_id.to_owned()),
+        &frozen,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "freeze_token")
+        .add_attribute("admin", info.sender)
+        .add_attribute("terra_collection", terra_collection)
+        .add_attribute("token_id", token_id))
+}
+
+/// Allows admins to resolve a dispute by unfreezing a token, or by sending it to `redirect_to`.
+/// Redirecting the token cancels its claim, failed release, and queued release.
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `info` - additional information about the message sender and attached funds
+/// * `collection` - the Terra collection's address
+/// * `token_id` - id of the frozen token
+/// * `redirect_to` - optional Terra address receiving the token. When omitted the token is unfrozen in place
+pub fn try_resolve_dispute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+    redirect_to: Option<String>,
+) -> Result<Response, ContractError> {
+    // Verify sender is an admin
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !check_is_admin(deps.storage, sender_raw)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let terra_collection = deps.api.addr_validate(&collection)?;
+    let key = (terra_collection.to_owned(), token_id.to_owned());
+    if !FROZEN_TOKENS.has(deps.storage, key.clone()) {
+        return Err(ContractError::TokenNotFrozen { token_id });
+    }
+    FROZEN_TOKENS.remove(deps.storage, key.clone());
+
+    let mut response = Response::new()
+        .add_attribute("action", "resolve_dispute")
+        .add_attribute("admin", info.sender.to_owned())
+        .add_attribute("terra_collection", terra_collection.to_owned())
+        .add_attribute("token_id", token_id.to_owned());
+    let recipient = match redirect_to {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => return Ok(response),
+    };
+
+    check_in_custody(&deps, &env, &terra_collection, &token_id)?;
+
+    // The token leaves the bridge, so nothing else may deliver it
+    if let Some(claim) = CLAIMS.may_load(deps.storage, key.clone())? {
+        remove_claim(deps.storage, key.clone(), &claim);
+    }
+    if let Some(queue_id) = QUEUED_TOKENS.may_load(deps.storage, key.clone())? {
+        let release = QUEUED_RELEASES.load(deps.storage, queue_id.into())?;
+        remove_queued_release(deps.storage, queue_id, &release);
+        response = response.add_attribute("cancelled_queue_id", queue_id.to_string());
+    }
+    FAILED_RELEASES.remove(deps.storage, key);
+    close_pending_bridge(
+        deps.storage,
+        &terra_collection,
+        &token_id,
+        BridgeStatus::Failed,
+    )?;
+    let escrow = remove_escrow(
+        deps.storage,
+        terra_collection.to_owned(),
+        token_id.to_owned(),
+    )?;
+
+    // Create & save history for the redirect
+    let record = BridgeRecord {
+        is_enter: false,
+        token_id: token_id.to_owned(),
+        source_address: Some(recipient.to_owned()),
+        source_collection: terra_collection.to_owned(),
+        destination_address: escrow.map(|escrow| escrow.destination_address),
+        destination_collection: TERRA_TO_SN_MAP
+            .may_load(deps.storage, terra_collection.to_owned())?
+            .unwrap_or_default(),
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+        payload_hash: None,
+        delivery_mode: Some(DeliveryMode::Transfer),
+        seq: None,
+        status: Some(BridgeStatus::Completed),
+        sn_tx_hash: None,
+    };
+    let history_id = save_history(
+        deps.storage,
+        terra_collection.to_owned(),
+        token_id.to_owned(),
+        record,
+    )?;
+
+    let send = WasmMsg::Execute {
+        contract_addr: terra_collection.to_string(),
+        msg: to_binary(&TransferNft {
+            recipient: recipient.to_string(),
+            token_id,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(response
+        .add_message(send)
+        .add_attribute("recipient", recipient)
+        .add_attribute("history_id", history_id.to_string())
+        .add_attributes(history_chain_attributes(deps.storage)?))
 }
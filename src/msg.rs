@@ -1,9 +1,14 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
 use cw721::Cw721ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::BridgeRecord;
+use crate::state::{
+    Bond, BondConfig, BridgeRecord, BridgeStatus, Challenge, Checkpoint, CircuitBreaker, Claim,
+    CollectionInfo, DeliveryMode, EscrowRecord, FailedRelease, FrozenToken, Heartbeat,
+    HistoryLinkRecord, MetadataSnapshot, QueuedRelease, RateLimits, RescueRecord, SlashProposal,
+    Trip, WithdrawalRecord, WithdrawnFunds,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -35,6 +40,100 @@ pub enum ExecuteMsg {
         remove: Option<Vec<String>>,
     },
 
+    /// Update the contract's guardians
+    UpdateGuardians {
+        /// The addresses to add
+        add: Option<Vec<String>>,
+        /// The addresses to remove
+        remove: Option<Vec<String>>,
+    },
+
+    /// Update the contract's watchers
+    UpdateWatchers {
+        /// The addresses to add
+        add: Option<Vec<String>>,
+        /// The addresses to remove
+        remove: Option<Vec<String>>,
+    },
+
+    /// Update the addresses that can not use the bridge
+    UpdateDenylist {
+        /// The Terra or SN addresses to deny
+        /// * stored lowercase. Addresses without the `secret` prefix must be valid Terra addresses
+        add: Option<Vec<String>>,
+        /// The Terra or SN addresses to allow again
+        remove: Option<Vec<String>>,
+        /// Optional external denylist contract that answers [DenylistQueryMsg] queries
+        /// * use an empty string to stop querying the contract
+        contract: Option<String>,
+    },
+
+    /// Update the sliding-window rate limits on receives and releases
+    UpdateRateLimits {
+        /// The new limits
+        /// * when omitted, all limits are removed
+        limits: Option<RateLimits>,
+    },
+
+    /// Update how long releases from a collection are queued before they can be executed
+    UpdateReleaseDelay {
+        /// The Terra collection's address
+        collection: String,
+        /// The number of seconds releases are queued for
+        /// * when omitted, releases from the collection are no longer queued
+        delay: Option<u64>,
+    },
+
+    /// Update how long every release is queued for so watchers can challenge it.
+    /// The period applies to releases from every collection, and while it is set tokens
+    /// can not be made claimable
+    UpdateChallengePeriod {
+        /// The number of seconds releases are queued for
+        /// * when omitted, only releases from time-locked collections are queued
+        period: Option<u64>,
+    },
+
+    /// Update how operators bond funds and how their bonds are slashed.
+    /// Admins can act as operators without a bond
+    UpdateBondConfig {
+        /// The new configuration. Its denom can not change while funds are bonded
+        /// * when omitted, operators no longer need a bond to be active. Only allowed once
+        ///   every bond has been claimed or slashed
+        config: Option<BondConfigMsg>,
+    },
+
+    /// Propose slashing an operator's bond after a bad release.
+    /// The proposal counts as the sender's approval and replaces any earlier proposal
+    ProposeSlash {
+        /// The operator's address
+        operator: String,
+        /// The amount to slash
+        amount: Uint128,
+        /// The bad release that justifies the slash
+        reason: String,
+    },
+
+    /// Approve the slash proposed for an operator.
+    /// The bond is slashed once enough admins approve
+    ApproveSlash {
+        /// The operator's address
+        operator: String,
+    },
+
+    /// Update how recently an operator must have sent a heartbeat for the bridge to receive tokens
+    UpdateLivenessWindow {
+        /// The number of seconds
+        /// * when omitted, tokens are received regardless of heartbeats
+        window: Option<u64>,
+    },
+
+    /// Update the conditions under which the bridge pauses itself
+    UpdateCircuitBreaker {
+        /// The new trip conditions
+        /// * when omitted, the circuit breaker is disabled
+        circuit_breaker: Option<CircuitBreaker>,
+    },
+
     /// Update the state of the bridge
     UpdatePause {
         /// `true` to move the bridge to the paused state,
@@ -44,6 +143,86 @@ pub enum ExecuteMsg {
         collection: Option<String>,
     },
 
+    /// Set how long a received NFT must remain unacknowledged before its depositor can reclaim it
+    UpdateReclaimTimeout {
+        /// The new timeout, in seconds
+        timeout: u64,
+    },
+
+    /// Permanently wind down the bridge.
+    /// * the bridge stops receiving NFTs and depositors can withdraw their escrowed NFTs
+    /// * nb: this can not be undone
+    WindDown {},
+
+    /// Rebuild the escrow ledger for tokens received before it was tracked.
+    /// A token is restored if its latest BridgeRecord is inbound and the bridge still owns it
+    BackfillEscrow {
+        /// The Terra collection's address
+        collection: String,
+        /// The token_id of the last token checked in the previous backfill. Used in pagination
+        start_after: Option<String>,
+        /// The maximum number of tokens to check. Used in pagination
+        limit: Option<u8>,
+    },
+
+    /// Return an NFT that was transferred to the bridge without `SendNft`
+    /// * only NFTs the bridge owns that are not in the escrow ledger can be rescued
+    RescueNft {
+        /// The Terra collection's address
+        collection: String,
+        /// The token_id for the NFT
+        token_id: String,
+        /// The Terra address receiving the NFT
+        recipient: String,
+    },
+
+    /// Withdraw native coins held by the bridge
+    WithdrawNative {
+        /// The Terra address receiving the coins
+        recipient: String,
+        /// The coins to withdraw
+        amount: Vec<Coin>,
+    },
+
+    /// Withdraw cw20 tokens held by the bridge
+    WithdrawCw20 {
+        /// The cw20 contract's address
+        token: String,
+        /// The Terra address receiving the tokens
+        recipient: String,
+        /// The amount of tokens to withdraw
+        amount: Uint128,
+    },
+
+    /// Freeze an NFT so it can not be received or released while a dispute is resolved
+    FreezeToken {
+        /// The Terra collection's address
+        collection: String,
+        /// The token_id for the NFT
+        token_id: String,
+        /// A note describing the dispute
+        note: String,
+    },
+
+    /// Resolve a frozen NFT's dispute
+    ResolveDispute {
+        /// The Terra collection's address
+        collection: String,
+        /// The token_id for the NFT
+        token_id: String,
+        /// Optional Terra address the NFT is sent to
+        /// * when omitted, the NFT is unfrozen and stays where it is
+        redirect_to: Option<String>,
+    },
+
+    /// Revoke an unclaimed NFT's claim, leaving the NFT in escrow
+    RevokeClaim {
+        /// The Terra collection's address
+        collection: String,
+        /// The token_id for the NFT
+        token_id: String,
+    },
+
     /*
      * Operator messages
      */
@@ -57,6 +236,19 @@ pub enum ExecuteMsg {
         remove: Option<Vec<CollectionMapping>>,
     },
 
+    /// Update the Terra addresses that can bridge a collection's NFTs while it is in allowlist-only mode
+    UpdateAllowlist {
+        /// The Terra collection's address
+        collection: String,
+        /// The addresses to add
+        add: Option<Vec<String>>,
+        /// The addresses to remove
+        remove: Option<Vec<String>>,
+        /// Optional field to turn allowlist-only mode on or off for a mapped collection
+        /// * only admins can change the mode
+        allowlist_only: Option<bool>,
+    },
+
     /// Transfer ownership of NFT to the new owner
     /// * contract_address, token_id is the key for our NFTs
     ReleaseNft {
@@ -68,13 +260,156 @@ pub enum ExecuteMsg {
         sn_address: String,
         /// The token_id for the NFT
         token_id: String,
-        /// True if the recipient address is a smart contract
-        recipient_is_contract: bool,
+        /// Overrides whether the recipient address is a smart contract.
+        /// * when omitted, the bridge queries the chain to determine this itself
+        recipient_is_contract: Option<bool>,
+        /// Optional message from the SN side that is forwarded verbatim to a contract recipient
+        /// * only valid when `recipient_is_contract` is true
+        msg: Option<Binary>,
+        /// The last SN event processed once this release is made
+        /// * can not be before the sender's current checkpoint
+        checkpoint: Option<Checkpoint>,
+    },
+
+    /// Transfer ownership of many NFTs in one message
+    ReleaseNfts {
+        /// The NFTs to release
+        releases: Vec<ReleaseRequest>,
+        /// Skip invalid releases instead of failing the whole batch
+        /// * skipped releases are listed in the response data as a [ReleaseNftsResponse]
+        skip_invalid: Option<bool>,
+        /// The last SN event processed once these releases are made
+        /// * can not be before the sender's current checkpoint
+        /// * not recorded if any release is skipped
+        checkpoint: Option<Checkpoint>,
+    },
+
+    /// Execute a queued release once it has unlocked
+    /// * can be sent by anyone
+    ExecuteQueuedRelease {
+        /// The id of the queued release
+        queue_id: u64,
+    },
+
+    /// Cancel a queued release before it is executed. The NFT stays in escrow
+    CancelQueuedRelease {
+        /// The id of the queued release
+        queue_id: u64,
+    },
+
+    /// Bond the attached funds. An operator is only active while its bond meets the minimum
+    Bond {},
+
+    /// Start unbonding funds. Only possible once the address is no longer an operator
+    Unbond {
+        /// The amount to unbond
+        amount: Uint128,
+    },
+
+    /// Claim funds that have finished unbonding
+    ClaimBond {},
+
+    /// Challenge a queued release before it unlocks. The release can not be executed and its
+    /// collection is paused
+    ChallengeRelease {
+        /// The id of the queued release
+        queue_id: u64,
+        /// Why the release is being challenged
+        reason: String,
+    },
+
+    /// Dismiss a watcher's challenge so the queued release can be executed.
+    /// The collection stays paused until an admin unpauses it
+    DismissChallenge {
+        /// The id of the queued release
+        queue_id: u64,
+    },
+
+    /// Make an escrowed NFT claimable by a Terra address instead of transferring it
+    MarkClaimable {
+        /// The Terra address allowed to claim the NFT
+        recipient: String,
+        /// The SN contract address for the NFT
+        sn_collection: String,
+        /// The SN address that initiated the request
+        sn_address: String,
+        /// The token_id for the NFT
+        token_id: String,
+        /// Optional number of seconds after which the NFT can no longer be claimed
+        expires_in: Option<u64>,
+    },
+
+    /// Record whether SN accepted a token received by the bridge
+    AcknowledgeBridge {
+        /// The sequence number of the received token
+        seq: u64,
+        /// The hash of the SN transaction that handled the token
+        sn_tx_hash: Option<String>,
+        /// true if SN accepted the token
+        success: bool,
+    },
+
+    /// Record the last SN event the sender's relayer fully processed
+    Checkpoint {
+        /// The SN position. Can not be before the sender's current checkpoint
+        checkpoint: Checkpoint,
+    },
+
+    /// Signal that the sender's relayer is alive
+    Heartbeat {
+        /// The last SN block height the relayer has seen
+        last_sn_height: u64,
+    },
+
+    /// Delay or block reclaiming a received NFT while SN is still processing it
+    HoldReclaim {
+        /// The sequence number of the received token
+        seq: u64,
+        /// The time (in seconds since 01/01/1970) before which the token can not be reclaimed
+        /// * when omitted, reclaiming is blocked until the token is acknowledged
+        until: Option<u64>,
+    },
+
+    /// Retry a release whose transfer failed, optionally redirecting the NFT to a new recipient
+    RetryRelease {
+        /// The Terra collection's address
+        collection: String,
+        /// The token_id for the NFT
+        token_id: String,
+        /// The Terra address of the new recipient. Defaults to the original recipient
+        recipient: Option<String>,
+        /// Overrides whether the recipient address is a smart contract
+        recipient_is_contract: Option<bool>,
+        /// Optional message forwarded verbatim to a contract recipient
+        msg: Option<Binary>,
     },
 
     /*
      * General messages
      */
+    /// Take back an NFT sent to the bridge that SN never acknowledged
+    /// * only the depositor can reclaim the NFT, once the reclaim timeout has passed
+    ReclaimNft {
+        /// The sequence number of the received token
+        seq: u64,
+    },
+
+    /// Withdraw an NFT the sender deposited once the bridge has been wound down
+    WithdrawNft {
+        /// The Terra collection's address
+        collection: String,
+        /// The token_id for the NFT
+        token_id: String,
+    },
+
+    /// Claim an NFT that was made claimable by the sender
+    ClaimNft {
+        /// The Terra collection's address
+        collection: String,
+        /// The token_id for the NFT
+        token_id: String,
+    },
+
     /// Accept cw721 NFT
     /// * https://docs.cosmwasm.com/cw-plus/0.9.0/cw721/spec/#receiver
     ReceiveNft(Cw721ReceiveMsg),
@@ -89,9 +424,49 @@ pub enum QueryMsg {
     /// Lists the contract's operators
     Operators {},
 
+    /// Lists the contract's guardians
+    Guardians {},
+
+    /// Lists the contract's watchers and the challenge period
+    Watchers {},
+
+    /// Lists the denied addresses and the external denylist contract
+    Denylist {
+        /// The last address from the previous query.
+        /// Used in pagination.
+        start_after: Option<String>,
+        /// The maximum number of addresses to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
     /// Returns the Secret network address associated with `source_contract` if a mapping exists.
     CollectionMappings { source_contracts: Vec<String> },
 
+    /// Shows whether a collection is in allowlist-only mode and `address` is allowlisted
+    Allowlist {
+        /// The Terra collection's address
+        collection: String,
+        /// The Terra address to check
+        address: String,
+    },
+
+    /// Shows whether the bridge is paused and why the circuit breaker paused it, if it did
+    PauseStatus {
+        /// The Terra collection to show the pause state for
+        collection: Option<String>,
+    },
+
+    /// Shows the rate limits and how much of the current window has been used
+    RateLimitUsage {
+        /// The Terra collection to show release usage for
+        collection: Option<String>,
+        /// The Terra address to show receive usage for
+        user: Option<String>,
+        /// The operator to show release usage for
+        operator: Option<String>,
+    },
+
     /// Lists the information for a given NFT
     HistoryByToken {
         /// The address of the collection you wish to view
@@ -105,8 +480,175 @@ pub enum QueryMsg {
         /// Used in pagination.
         limit: Option<u8>,
     },
+
+    /// Lists the tokens from a collection that are currently held by the bridge
+    EscrowedTokens {
+        /// The address of the collection you wish to view
+        collection: String,
+        /// The token_id of the last element from the previous query.
+        /// Used in pagination.
+        start_after: Option<String>,
+        /// The maximum number of tokens to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Lists the tokens currently held by the bridge that were sent by `address`
+    EscrowedTokensByDepositor {
+        /// The Terra address that sent the tokens to the bridge
+        address: String,
+        /// The collection and token_id of the last element from the previous query.
+        /// Used in pagination.
+        start_after: Option<TokenKey>,
+        /// The maximum number of tokens to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Shows the metadata snapshot taken the last time a token was received
+    TokenMetadata {
+        /// The address of the collection you wish to view
+        collection: String,
+        /// The token_id of the NFT
+        token_id: String,
+    },
+
+    /// Lists received tokens that SN has not acknowledged, oldest first
+    PendingBridges {
+        /// Only show records at least this many seconds old
+        older_than: Option<u64>,
+        /// The sequence number of the last record from the previous query.
+        /// Used in pagination.
+        start_after: Option<u64>,
+        /// The maximum number of records to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Lists the frozen NFTs from a collection
+    FrozenTokens {
+        /// The Terra collection's address
+        collection: String,
+        /// The token_id of the last NFT from the previous query.
+        /// Used in pagination.
+        start_after: Option<String>,
+        /// The maximum number of NFTs to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Lists the releases waiting in the time-lock queue, oldest first
+    QueuedReleases {
+        /// The id of the last queued release from the previous query.
+        /// Used in pagination.
+        start_after: Option<u64>,
+        /// The maximum number of releases to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Lists the NFTs admins have rescued, newest first
+    Rescues {
+        /// The id of the last rescue from the previous query.
+        /// Used in pagination.
+        start_after: Option<u64>,
+        /// The maximum number of rescues to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Lists the native coins held by the bridge
+    Balances {},
+
+    /// Lists the operators' bonds and the bond configuration
+    Bonds {
+        /// The address of the last operator from the previous query.
+        /// Used in pagination.
+        start_after: Option<String>,
+        /// The maximum number of bonds to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Lists the funds admins have withdrawn, newest first
+    Withdrawals {
+        /// The id of the last withdrawal from the previous query.
+        /// Used in pagination.
+        start_after: Option<u64>,
+        /// The maximum number of withdrawals to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Shows the head of the hash chain over every write to the bridge history
+    HistoryChain {},
+
+    /// Lists the links of the hash chain over the bridge history, oldest first
+    HistoryLinks {
+        /// The sequence number of the last link from the previous query.
+        /// Used in pagination.
+        start_after: Option<u64>,
+        /// The maximum number of links to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Shows the last SN event processed by any operator, and by a given operator
+    Checkpoint {
+        /// The operator to show the checkpoint for
+        operator: Option<String>,
+    },
+
+    /// Lists the operators' latest heartbeats and whether the bridge is receiving tokens
+    Heartbeats {
+        /// The address of the last operator from the previous query.
+        /// Used in pagination.
+        start_after: Option<String>,
+        /// The maximum number of heartbeats to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Summarizes the NFTs that remain in escrow, and whether the bridge has been wound down
+    WindDownStatus {},
+
+    /// Lists the NFTs that `address` can claim
+    Claims {
+        /// The Terra address allowed to claim the NFTs
+        address: String,
+        /// The collection and token_id of the last element from the previous query.
+        /// Used in pagination.
+        start_after: Option<TokenKey>,
+        /// The maximum number of claims to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Lists the releases from a collection whose transfers failed
+    FailedReleases {
+        /// The address of the collection you wish to view
+        collection: String,
+        /// The token_id of the last element from the previous query.
+        /// Used in pagination.
+        start_after: Option<String>,
+        /// The maximum number of releases to show.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
+
+    /// Compares the escrow recorded for a collection against the collection's live ownership
+    /// * requires the collection to implement the cw721 enumerable extension
+    ReconcileEscrow {
+        /// The address of the collection you wish to reconcile
+        collection: String,
+        /// The `last_token_id` from the previous query.
+        /// Used in pagination.
+        start_after: Option<String>,
+        /// The maximum number of tokens to check.
+        /// Used in pagination.
+        limit: Option<u8>,
+    },
     // TODO: add ContractInfo query showing:
-    // is paused
     // admins
     // operators
 }
@@ -125,12 +667,84 @@ pub enum QueryMsg {
 pub struct CollectionMapping {
     pub source: String,
     pub destination: String,
+    /// Only allowlisted Terra addresses can bridge the collection's NFTs when true
+    pub allowlist_only: Option<bool>,
+}
+
+/// How operators bond funds to become active and how their bonds are slashed
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BondConfigMsg {
+    /// The native denom bonds are paid in
+    pub denom: String,
+    /// The minimum bond an operator needs to be active
+    pub min_bond: Uint128,
+    /// The number of seconds unbonded funds are held before they can be claimed
+    pub unbonding_period: u64,
+    /// The Terra address slashed funds are sent to
+    pub treasury: String,
+    /// The number of admins that must approve a slash
+    pub slash_approvals: u32,
+}
+
+/// A single release in a `ReleaseNfts` batch
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ReleaseRequest {
+    /// The Terra address of the recipient
+    pub recipient: String,
+    /// The SN contract address for the NFT
+    pub sn_collection: String,
+    /// The SN address that initiated the request
+    pub sn_address: String,
+    /// The token_id for the NFT
+    pub token_id: String,
+    /// Overrides whether the recipient address is a smart contract
+    pub recipient_is_contract: Option<bool>,
+    /// Optional message forwarded verbatim to a contract recipient
+    pub msg: Option<Binary>,
+}
+
+/// Response data for `ReleaseNfts`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ReleaseNftsResponse {
+    /// The releases that were skipped because they were invalid
+    pub skipped: Vec<SkippedRelease>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SkippedRelease {
+    /// position of the release in the batch
+    pub index: u32,
+    /// id of the token that was not released
+    pub token_id: String,
+    /// why the release is invalid
+    pub error: String,
+}
+
+/// The query an external denylist contract must answer
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DenylistQueryMsg {
+    IsDenied { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct IsDeniedResponse {
+    pub denied: bool,
 }
 
 /*
  * Query Utils
  */
 
+/// A token in a list that spans collections
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenKey {
+    /// The Terra collection's address
+    pub collection: String,
+    /// The token_id for the NFT
+    pub token_id: String,
+}
+
 /// Contract configuration
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ContractInfoResponse {}
@@ -149,9 +763,76 @@ pub struct OperatorsResponse {
     pub operators: Vec<Addr>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GuardiansResponse {
+    /// A list of all contract guardians
+    pub guardians: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WatchersResponse {
+    /// A list of all contract watchers
+    pub watchers: Vec<Addr>,
+    /// The number of seconds every release is queued for, if set
+    pub challenge_period: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DenylistResponse {
+    /// The denied Terra and SN addresses
+    pub addresses: Vec<String>,
+    /// The external denylist contract
+    pub contract: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PauseStatusResponse {
+    /// true if the whole bridge is paused
+    pub is_paused: bool,
+    /// the reason the circuit breaker paused the bridge, if it did
+    pub trip: Option<Trip>,
+    /// true if the requested collection is paused
+    pub is_collection_paused: Option<bool>,
+    /// the reason the circuit breaker paused the requested collection, if it did
+    pub collection_trip: Option<Trip>,
+    /// the conditions under which the bridge pauses itself, if any are configured
+    pub circuit_breaker: Option<CircuitBreaker>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RateLimitUsageResponse {
+    /// The rate limits, if any are configured
+    pub limits: Option<RateLimits>,
+    /// Releases from the requested collection in the current window
+    pub collection: Option<WindowUsageResponse>,
+    /// Tokens received from the requested user in the current window
+    pub user: Option<WindowUsageResponse>,
+    /// Releases made by the requested operator in the current window
+    pub operator: Option<WindowUsageResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WindowUsageResponse {
+    /// the number of actions in the current window
+    pub used: u32,
+    /// the maximum number of actions per window
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AllowlistResponse {
+    /// true if only allowlisted addresses can bridge the collection's NFTs
+    pub allowlist_only: bool,
+    /// true if the address is allowlisted for the collection
+    pub is_allowlisted: bool,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct CollectionMappingResponse {
     pub destinations: Vec<String>,
+    /// The cw721 contract info recorded for each source contract, in the same order as `destinations`.
+    /// * `None` for mappings that were added before contract info was recorded
+    pub collection_info: Vec<Option<CollectionInfo>>,
 }
 
 /// Shows all bridge record for a specific token
@@ -179,6 +860,16 @@ pub struct BridgeRecordResponse {
     pub block_height: u64,
     /// the time (in seconds since 01/01/1970) of tx
     pub block_time: u64,
+    /// the SHA-256 hash of the payload forwarded to a contract recipient on release
+    pub payload_hash: Option<Binary>,
+    /// the cw721 message used to deliver the token on release
+    pub delivery_mode: Option<DeliveryMode>,
+    /// the bridge-wide sequence number of a received token, used to acknowledge it
+    pub seq: Option<u64>,
+    /// the lifecycle status of the transaction
+    pub status: Option<BridgeStatus>,
+    /// the hash of the SN transaction that acknowledged the token
+    pub sn_tx_hash: Option<String>,
 }
 
 impl From<BridgeRecord> for BridgeRecordResponse {
@@ -198,6 +889,450 @@ impl From<BridgeRecord> for BridgeRecordResponse {
             destination_collection: record.destination_collection,
             block_height: record.block_height,
             block_time: record.block_time,
+            payload_hash: record.payload_hash,
+            delivery_mode: record.delivery_mode,
+            seq: record.seq,
+            status: record.status,
+            sn_tx_hash: record.sn_tx_hash,
+        }
+    }
+}
+
+/// Shows received tokens that SN has not acknowledged
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PendingBridgesResponse {
+    /// The pending records, oldest first
+    pub bridges: Vec<PendingBridgeResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PendingBridgeResponse {
+    /// the sequence number used to acknowledge the record
+    pub seq: u64,
+    /// the id of the record in the token's history
+    pub history_id: u64,
+    /// the time (in seconds since 01/01/1970) from which the depositor can reclaim the token.
+    /// * `None` if an operator has blocked reclaiming it
+    pub reclaimable_at: Option<u64>,
+    /// the pending record
+    pub record: BridgeRecordResponse,
+}
+
+/// Shows frozen tokens
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FrozenTokensResponse {
+    /// The frozen tokens
+    pub tokens: Vec<FrozenTokenResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FrozenTokenResponse {
+    /// id of the frozen token
+    pub token_id: String,
+    /// the dispute note attached by the admin
+    pub note: String,
+    /// the admin that froze the token
+    pub admin: String,
+    /// the time (in seconds since 01/01/1970) the token was frozen
+    pub frozen_at: u64,
+}
+
+impl FrozenTokenResponse {
+    pub fn new(token_id: String, frozen: FrozenToken) -> Self {
+        Self {
+            token_id,
+            note: frozen.note,
+            admin: frozen.admin.into_string(),
+            frozen_at: frozen.frozen_at,
+        }
+    }
+}
+
+/// Shows releases waiting in the time-lock queue
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct QueuedReleasesResponse {
+    /// The queued releases, oldest first
+    pub releases: Vec<QueuedReleaseResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct QueuedReleaseResponse {
+    /// id of the queued release
+    pub queue_id: u64,
+    /// the address of the Terra collection
+    pub collection: String,
+    /// id of the queued token
+    pub token_id: String,
+    /// the Terra address receiving the token
+    pub recipient: String,
+    /// the address of the SN collection
+    pub sn_collection: String,
+    /// the SN address that initiated the release
+    pub sn_address: String,
+    /// the time (in seconds since 01/01/1970) the release was queued
+    pub queued_at: u64,
+    /// the time (in seconds since 01/01/1970) from which the release can be executed
+    pub unlock_at: u64,
+    /// the watcher's challenge, if the release was challenged
+    pub challenge: Option<Challenge>,
+}
+
+impl QueuedReleaseResponse {
+    pub fn new(queue_id: u64, release: QueuedRelease) -> Self {
+        Self {
+            queue_id,
+            collection: release.collection.into_string(),
+            token_id: release.token_id,
+            recipient: release.recipient.into_string(),
+            sn_collection: release.sn_collection,
+            sn_address: release.sn_address,
+            queued_at: release.queued_at,
+            unlock_at: release.unlock_at,
+            challenge: release.challenge,
+        }
+    }
+}
+
+/// Shows NFTs admins have rescued
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RescuesResponse {
+    /// The rescues, newest first
+    pub rescues: Vec<RescueResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RescueResponse {
+    /// id of the rescue
+    pub rescue_id: u64,
+    /// the address of the Terra collection
+    pub collection: String,
+    /// id of the rescued token
+    pub token_id: String,
+    /// the Terra address the token was returned to
+    pub recipient: String,
+    /// the admin that rescued the token
+    pub admin: String,
+    /// the Terra block of the tx
+    pub block_height: u64,
+    /// the time (in seconds since 01/01/1970) of tx
+    pub block_time: u64,
+}
+
+impl RescueResponse {
+    pub fn new(rescue_id: u64, record: RescueRecord) -> Self {
+        Self {
+            rescue_id,
+            collection: record.collection.into_string(),
+            token_id: record.token_id,
+            recipient: record.recipient.into_string(),
+            admin: record.admin.into_string(),
+            block_height: record.block_height,
+            block_time: record.block_time,
+        }
+    }
+}
+
+/// Shows the native coins held by the bridge
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BalancesResponse {
+    /// The native balances
+    pub balances: Vec<Coin>,
+}
+
+/// Shows the operators' bonds
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BondsResponse {
+    /// How operators bond funds, if bonding is enabled
+    pub config: Option<BondConfig>,
+    /// The bonds
+    pub bonds: Vec<BondResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BondResponse {
+    /// the operator's address
+    pub operator: String,
+    /// the operator's bond
+    pub bond: Bond,
+    /// true if the address is an operator with a large enough bond
+    pub is_active: bool,
+    /// the slash proposed against the operator, if any
+    pub slash_proposal: Option<SlashProposal>,
+}
+
+/// Shows funds admins have withdrawn
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawalsResponse {
+    /// The withdrawals, newest first
+    pub withdrawals: Vec<WithdrawalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawalResponse {
+    /// id of the withdrawal
+    pub withdrawal_id: u64,
+    /// the withdrawn funds
+    pub funds: WithdrawnFunds,
+    /// the Terra address the funds were sent to
+    pub recipient: String,
+    /// the admin that withdrew the funds
+    pub admin: String,
+    /// the Terra block of the tx
+    pub block_height: u64,
+    /// the time (in seconds since 01/01/1970) of tx
+    pub block_time: u64,
+}
+
+impl WithdrawalResponse {
+    pub fn new(withdrawal_id: u64, record: WithdrawalRecord) -> Self {
+        Self {
+            withdrawal_id,
+            funds: record.funds,
+            recipient: record.recipient.into_string(),
+            admin: record.admin.into_string(),
+            block_height: record.block_height,
+            block_time: record.block_time,
+        }
+    }
+}
+
+/// Shows the head of the hash chain over every write to the bridge history
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HistoryChainResponse {
+    /// The number of history writes chained so far
+    pub seq: u64,
+    /// The current head hash, or `None` if nothing has been written to the history yet
+    pub head: Option<Binary>,
+}
+
+/// Shows links of the hash chain over the bridge history
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HistoryLinksResponse {
+    /// The links, oldest first
+    pub links: Vec<HistoryLinkResponse>,
+}
+
+/// A link of the hash chain. The head is the SHA-256 of the previous link's head followed by
+/// the canonical JSON encoding of [crate::state::HistoryLink] built from this link
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HistoryLinkResponse {
+    /// the sequence number of the link
+    pub seq: u64,
+    /// the address of the Terra collection
+    pub collection: String,
+    /// id of bridged token
+    pub token_id: String,
+    /// the id of the record in the token's history
+    pub history_id: u64,
+    /// the record as written
+    pub record: BridgeRecord,
+    /// the head of the chain once this link was added
+    pub head: Binary,
+}
+
+impl HistoryLinkResponse {
+    pub fn new(seq: u64, link: HistoryLinkRecord) -> Self {
+        Self {
+            seq,
+            collection: link.collection.into_string(),
+            token_id: link.token_id,
+            history_id: link.history_id,
+            record: link.record,
+            head: link.head,
+        }
+    }
+}
+
+/// Shows where relayers can resume scanning SN
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CheckpointResponse {
+    /// The last SN event processed by any operator
+    pub global: Option<Checkpoint>,
+    /// The last SN event processed by the requested operator
+    pub operator: Option<Checkpoint>,
+}
+
+/// Shows the operators' latest heartbeats
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HeartbeatsResponse {
+    /// The number of seconds since the latest heartbeat after which tokens are not received
+    pub liveness_window: Option<u64>,
+    /// false if no operator has sent a heartbeat within the liveness window
+    pub is_live: bool,
+    /// The heartbeats
+    pub heartbeats: Vec<HeartbeatResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HeartbeatResponse {
+    /// the operator's address
+    pub operator: String,
+    /// the operator's latest heartbeat
+    pub heartbeat: Heartbeat,
+}
+
+/// Shows whether the bridge has been wound down and what remains in escrow
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WindDownStatusResponse {
+    /// the time (in seconds since 01/01/1970) the bridge was wound down
+    pub wound_down_at: Option<u64>,
+    /// the total number of tokens that remain in escrow
+    pub total_remaining: u64,
+    /// the number of tokens that remain in escrow for each collection
+    pub collections: Vec<RemainingTokensResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RemainingTokensResponse {
+    /// the address of the Terra collection
+    pub collection: String,
+    /// the number of its tokens that remain in escrow
+    pub remaining: u64,
+}
+
+/// Shows tokens currently held in escrow by the bridge
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EscrowedTokensResponse {
+    /// The escrowed tokens
+    pub tokens: Vec<EscrowedTokenResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EscrowedTokenResponse {
+    /// the address of the Terra collection
+    pub collection: String,
+    /// id of the escrowed token
+    pub token_id: String,
+    /// the Terra address that sent the token to the bridge
+    pub depositor: String,
+    /// the SN address that the token was bridged to
+    pub destination_address: String,
+    /// the Terra block the token was received in
+    pub block_height: u64,
+}
+
+impl EscrowedTokenResponse {
+    pub fn new(collection: Addr, token_id: String, record: EscrowRecord) -> Self {
+        Self {
+            collection: collection.into_string(),
+            token_id,
+            depositor: record.depositor.into_string(),
+            destination_address: record.destination_address,
+            block_height: record.block_height,
+        }
+    }
+}
+
+/// Shows the differences between the recorded escrow and the collection's live ownership
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ReconcileEscrowResponse {
+    /// Tokens recorded in escrow that the bridge does not own
+    pub missing: Vec<MissingTokenResponse>,
+    /// Tokens owned by the bridge that are not recorded in escrow
+    pub unexpected: Vec<String>,
+    /// The last token_id covered by this page, or `None` if all tokens have been checked
+    pub last_token_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MissingTokenResponse {
+    /// id of the escrowed token
+    pub token_id: String,
+    /// the Terra address that sent the token to the bridge
+    pub depositor: String,
+    /// the current owner according to the collection, or `None` if the token was not found
+    pub owner: Option<String>,
+}
+
+/// Shows NFTs awaiting a claim
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ClaimsResponse {
+    /// The claimable NFTs
+    pub claims: Vec<ClaimResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ClaimResponse {
+    /// the address of the Terra collection
+    pub collection: String,
+    /// id of the claimable token
+    pub token_id: String,
+    /// the address of the SN collection
+    pub sn_collection: String,
+    /// the SN address that initiated the release
+    pub sn_address: String,
+    /// the time (in seconds since 01/01/1970) after which the token can no longer be claimed
+    pub expires_at: Option<u64>,
+}
+
+impl ClaimResponse {
+    pub fn new(collection: Addr, token_id: String, claim: Claim) -> Self {
+        Self {
+            collection: collection.into_string(),
+            token_id,
+            sn_collection: claim.sn_collection,
+            sn_address: claim.sn_address,
+            expires_at: claim.expires_at,
+        }
+    }
+}
+
+/// Shows releases whose transfers failed
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FailedReleasesResponse {
+    /// The failed releases
+    pub releases: Vec<FailedReleaseResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FailedReleaseResponse {
+    /// id of the token that is still in escrow
+    pub token_id: String,
+    /// the Terra address that should have received the token
+    pub recipient: String,
+    /// the address of the SN collection
+    pub sn_collection: String,
+    /// the SN address that initiated the release
+    pub sn_address: String,
+    /// the id of the bridge record saved for the release
+    pub history_id: u64,
+    /// the error returned by the Terra collection
+    pub error: String,
+    /// the Terra block the release failed in
+    pub block_height: u64,
+}
+
+impl FailedReleaseResponse {
+    pub fn new(token_id: String, failed: FailedRelease) -> Self {
+        Self {
+            token_id,
+            recipient: failed.recipient.into_string(),
+            sn_collection: failed.sn_collection,
+            sn_address: failed.sn_address,
+            history_id: failed.history_id,
+            error: failed.error,
+            block_height: failed.block_height,
+        }
+    }
+}
+
+/// Shows the metadata snapshot for a single token
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenMetadataResponse {
+    /// the raw cw721 `NftInfo` response returned by the Terra collection
+    pub nft_info: Binary,
+    /// the SHA-256 hash of `nft_info`
+    pub hash: Binary,
+    /// the id of the bridge record saved when the snapshot was taken
+    pub history_id: u64,
+}
+
+impl From<MetadataSnapshot> for TokenMetadataResponse {
+    fn from(snapshot: MetadataSnapshot) -> Self {
+        Self {
+            nft_info: snapshot.nft_info,
+            hash: snapshot.hash,
+            history_id: snapshot.history_id,
         }
     }
 }
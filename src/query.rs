@@ -1,13 +1,35 @@
-use cosmwasm_std::{to_binary, Addr, Binary, CanonicalAddr, Deps, Order, StdResult};
-use cw_storage_plus::Bound;
+use cosmwasm_std::{to_binary, Addr, Binary, CanonicalAddr, Deps, Env, Order, StdError, StdResult};
+use cw721::{Cw721QueryMsg, TokensResponse};
+use cw_storage_plus::{Bound, PrimaryKey};
 
 use crate::{
     error::ContractError,
     msg::{
-        AdminsResponse, BridgeRecordResponse, CollectionMappingResponse, HistoryResponse,
-        OperatorsResponse,
+        AdminsResponse, AllowlistResponse, BalancesResponse, BondResponse, BondsResponse,
+        BridgeRecordResponse, CheckpointResponse, ClaimResponse, ClaimsResponse,
+        CollectionMappingResponse, DenylistResponse, EscrowedTokenResponse, EscrowedTokensResponse,
+        FailedReleaseResponse, FailedReleasesResponse, FrozenTokenResponse, FrozenTokensResponse,
+        GuardiansResponse, HeartbeatResponse, HeartbeatsResponse, HistoryChainResponse,
+        HistoryLinkResponse, HistoryLinksResponse, HistoryResponse, MissingTokenResponse,
+        OperatorsResponse, PauseStatusResponse, PendingBridgeResponse, PendingBridgesResponse,
+        QueuedReleaseResponse, QueuedReleasesResponse, RateLimitUsageResponse,
+        ReconcileEscrowResponse, RemainingTokensResponse, RescueResponse, RescuesResponse,
+        TokenKey, TokenMetadataResponse, WatchersResponse, WindDownStatusResponse,
+        WindowUsageResponse, WithdrawalResponse, WithdrawalsResponse,
+    },
+    state::{
+        window_usage, CollectionInfo, EscrowRecord, RateScope, ADMINS, ALLOWLIST, ALLOWLIST_ONLY,
+        BONDS, BOND_CONFIG, BRIDGE_TRIP, CHALLENGE_PERIOD, CHECKPOINT, CHECKPOINTS,
+        CIRCUIT_BREAKER, CLAIMS, CLAIMS_BY_RECIPIENT, COLLECTION_INFO, COLL_TRIPS, DEFAULT_LIMIT,
+        DENYLIST, DENYLIST_CONTRACT, ESCROW, ESCROW_BY_DEPOSITOR, ESCROW_COUNT, FAILED_RELEASES,
+        FROZEN_TOKENS, GUARDIANS, HEARTBEATS, HISTORY, HISTORY_CHAIN, HISTORY_LINKS,
+        IS_COLL_PAUSED, IS_PAUSED, LIVENESS_WINDOW, MAX_LIMIT, METADATA, OPERS, PENDING_BRIDGES,
+        QUEUED_RELEASES, RATE_LIMITS, RESCUES, SLASH_PROPOSALS, TERRA_TO_SN_MAP, WATCHERS,
+        WITHDRAWALS, WOUND_DOWN_AT,
+    },
+    utils::{
+        check_is_bonded, check_is_live, normalize_denied_address, query_owner_of, reclaimable_at,
     },
-    state::{ADMINS, DEFAULT_LIMIT, HISTORY, MAX_LIMIT, OPERS, TERRA_TO_SN_MAP},
 };
 
 /*
@@ -48,6 +70,69 @@ pub fn query_operators(deps: Deps) -> Result<Binary, ContractError> {
     Ok(to_binary(&resp)?)
 }
 
+/// Fetches all guardians
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+pub fn query_guardians(deps: Deps) -> Result<Binary, ContractError> {
+    let guardians = GUARDIANS.may_load(deps.storage)?.unwrap_or_default();
+    let resp = GuardiansResponse {
+        guardians: guardians
+            .iter()
+            .map(|addr| deps.api.addr_humanize(addr))
+            .collect::<StdResult<Vec<Addr>>>()?,
+    };
+    Ok(to_binary(&resp)?)
+}
+
+/// Fetches all watchers and the challenge period
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+pub fn query_watchers(deps: Deps) -> Result<Binary, ContractError> {
+    let watchers = WATCHERS.may_load(deps.storage)?.unwrap_or_default();
+    let resp = WatchersResponse {
+        watchers: watchers
+            .iter()
+            .map(|addr| deps.api.addr_humanize(addr))
+            .collect::<StdResult<Vec<Addr>>>()?,
+        challenge_period: CHALLENGE_PERIOD.may_load(deps.storage)?,
+    };
+    Ok(to_binary(&resp)?)
+}
+
+/// Fetches the denied addresses and the external denylist contract
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `start_after` - The last address in the previous query. Used in pagination.
+/// * `limit` - The maximum number of addresses to fetch. Used in pagination
+pub fn query_denylist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|s| normalize_denied_address(deps.api, &s))
+        .transpose()?
+        .map(|s| Bound::Exclusive(s.into_bytes()));
+
+    let addresses = DENYLIST
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| Ok(String::from_utf8(key).map_err(StdError::from)?))
+        .collect::<Result<Vec<String>, ContractError>>()?;
+
+    Ok(to_binary(&DenylistResponse {
+        addresses,
+        contract: DENYLIST_CONTRACT.may_load(deps.storage)?,
+    })?)
+}
+
 /// Fetches the destination addresses that correspond to the `source_contracts`
 ///
 /// # Arguments
@@ -58,20 +143,126 @@ pub fn query_collection_mappings(
     deps: Deps,
     source_contracts: Vec<String>,
 ) -> Result<Binary, ContractError> {
-    let destinations = source_contracts
+    let (destinations, collection_info) = source_contracts
         .iter()
         .map(|addr| {
             let addr = deps.api.addr_validate(addr)?;
             let destination = TERRA_TO_SN_MAP
                 .may_load(deps.storage, addr.clone())?
                 .ok_or(ContractError::MappingNotFound {
-                    source_addr: addr.into_string(),
+                    source_addr: addr.to_string(),
                 })?;
-            Ok(destination)
+            let info = COLLECTION_INFO.may_load(deps.storage, addr)?;
+            Ok((destination, info))
         })
-        .collect::<Result<Vec<String>, ContractError>>()?;
+        .collect::<Result<Vec<(String, Option<CollectionInfo>)>, ContractError>>()?
+        .into_iter()
+        .unzip();
+
+    Ok(to_binary(&CollectionMappingResponse {
+        destinations,
+        collection_info,
+    })?)
+}
+
+/// Fetches whether a collection is in allowlist-only mode and `address` is allowlisted for it
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `collection` - The Terra collection's address
+/// * `address` - The Terra address to check
+pub fn query_allowlist(
+    deps: Deps,
+    collection: String,
+    address: String,
+) -> Result<Binary, ContractError> {
+    let coll_addr = deps.api.addr_validate(&collection)?;
+    let addr = deps.api.addr_validate(&address)?;
+
+    Ok(to_binary(&AllowlistResponse {
+        allowlist_only: ALLOWLIST_ONLY
+            .may_load(deps.storage, coll_addr.to_owned())?
+            .unwrap_or(false),
+        is_allowlisted: ALLOWLIST.has(deps.storage, (coll_addr, addr)),
+    })?)
+}
+
+/// Fetches whether the bridge is paused and why the circuit breaker paused it, if it did
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `collection` - Optional Terra collection to fetch the pause state for
+pub fn query_pause_status(deps: Deps, collection: Option<String>) -> Result<Binary, ContractError> {
+    let (is_collection_paused, collection_trip) = match collection {
+        Some(collection) => {
+            let collection = deps.api.addr_validate(&collection)?;
+            let is_paused = IS_COLL_PAUSED
+                .may_load(deps.storage, collection.to_owned())?
+                .unwrap_or(false);
+            (
+                Some(is_paused),
+                COLL_TRIPS.may_load(deps.storage, collection)?,
+            )
+        }
+        None => (None, None),
+    };
+
+    Ok(to_binary(&PauseStatusResponse {
+        is_paused: IS_PAUSED.load(deps.storage)?,
+        trip: BRIDGE_TRIP.may_load(deps.storage)?,
+        is_collection_paused,
+        collection_trip,
+        circuit_breaker: CIRCUIT_BREAKER.may_load(deps.storage)?,
+    })?)
+}
+
+/// Fetches the rate limits and how much of the current window has been used
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `collection` - Optional Terra collection to fetch release usage for
+/// * `user` - Optional Terra address to fetch receive usage for
+/// * `operator` - Optional operator to fetch release usage for
+pub fn query_rate_limit_usage(
+    deps: Deps,
+    env: Env,
+    collection: Option<String>,
+    user: Option<String>,
+    operator: Option<String>,
+) -> Result<Binary, ContractError> {
+    let limits = RATE_LIMITS.may_load(deps.storage)?;
+    let usage = |scope: RateScope, subject: Option<String>| -> StdResult<_> {
+        let subject = match subject {
+            Some(subject) => deps.api.addr_validate(&subject)?,
+            None => return Ok(None),
+        };
+        let (window, limit) = match &limits {
+            Some(limits) => (limits.window, scope.limit(limits)),
+            None => (0, None),
+        };
+        let used = window_usage(
+            deps.storage,
+            scope,
+            subject.as_str(),
+            window,
+            env.block.time.seconds(),
+        )?;
+        Ok(Some(WindowUsageResponse {
+            used: used.len() as u32,
+            limit,
+        }))
+    };
 
-    Ok(to_binary(&CollectionMappingResponse { destinations })?)
+    Ok(to_binary(&RateLimitUsageResponse {
+        collection: usage(RateScope::Collection, collection)?,
+        user: usage(RateScope::User, user)?,
+        operator: usage(RateScope::Operator, operator)?,
+        limits,
+    })?)
 }
 
 /// Fetches the history for a single token
@@ -105,3 +296,613 @@ pub fn query_history(
 
     Ok(to_binary(&HistoryResponse { history })?)
 }
+
+/// Fetches the tokens from a collection that are currently held in escrow
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `collection` - The Terra collection's address
+/// * `start_after` - The token_id of the last token received in the previous query. Used in pagination.
+/// * `limit` - The maximum number of tokens to fetch. Used in pagination
+pub fn query_escrowed_tokens(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let coll_addr = deps.api.addr_validate(&collection)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::Exclusive(s.into_bytes()));
+
+    let tokens = ESCROW
+        .prefix(coll_addr.to_owned())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (key, record) = pair?;
+            let token_id = String::from_utf8(key).map_err(StdError::from)?;
+            Ok(EscrowedTokenResponse::new(
+                coll_addr.to_owned(),
+                token_id,
+                record,
+            ))
+        })
+        .collect::<Result<Vec<EscrowedTokenResponse>, ContractError>>()?;
+
+    Ok(to_binary(&EscrowedTokensResponse { tokens })?)
+}
+
+/// Fetches the tokens currently held in escrow that were sent by `address`
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `address` - The Terra address that sent the tokens to the bridge
+/// * `start_after` - The collection and token_id of the last token received in the previous query.
+///   Used in pagination.
+/// * `limit` - The maximum number of tokens to fetch. Used in pagination
+pub fn query_escrowed_tokens_by_depositor(
+    deps: Deps,
+    address: String,
+    start_after: Option<TokenKey>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let depositor = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|key| -> StdResult<_> {
+            let collection = deps.api.addr_validate(&key.collection)?;
+            Ok(Bound::Exclusive((collection, key.token_id).joined_key()))
+        })
+        .transpose()?;
+
+    let tokens = ESCROW_BY_DEPOSITOR
+        .sub_prefix(depositor)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (collection, token_id) = pair?.1;
+            let record = ESCROW.load(deps.storage, (collection.to_owned(), token_id.to_owned()))?;
+            Ok(EscrowedTokenResponse::new(collection, token_id, record))
+        })
+        .collect::<Result<Vec<EscrowedTokenResponse>, ContractError>>()?;
+
+    Ok(to_binary(&EscrowedTokensResponse { tokens })?)
+}
+
+/// Fetches received tokens that SN has not acknowledged, oldest first
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `older_than` - Only fetch records at least this many seconds old
+/// * `start_after` - The sequence number of the last record in the previous query. Used in pagination.
+/// * `limit` - The maximum number of records to fetch. Used in pagination
+pub fn query_pending_bridges(
+    deps: Deps,
+    env: Env,
+    older_than: Option<u64>,
+    start_after: Option<u64>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::Exclusive(s.to_be_bytes().into()));
+    let cutoff = env
+        .block
+        .time
+        .seconds()
+        .saturating_sub(older_than.unwrap_or(0));
+
+    let bridges = PENDING_BRIDGES
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|pair| {
+            let (seq, (collection, token_id, history_id)) = pair?;
+            let record = HISTORY.load(deps.storage, (collection, token_id, history_id.into()))?;
+            let seq = u64::from_be_bytes(
+                seq.as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid sequence number"))?,
+            );
+            Ok(PendingBridgeResponse {
+                seq,
+                history_id,
+                reclaimable_at: reclaimable_at(deps.storage, seq, &record)?,
+                record: record.into(),
+            })
+        })
+        // Records are ordered by sequence number, so newer records can end the scan early
+        .take_while(|bridge: &Result<PendingBridgeResponse, ContractError>| {
+            bridge
+                .as_ref()
+                .map_or(true, |bridge| bridge.record.block_time <= cutoff)
+        })
+        .take(limit)
+        .collect::<Result<Vec<PendingBridgeResponse>, ContractError>>()?;
+
+    Ok(to_binary(&PendingBridgesResponse { bridges })?)
+}
+
+/// Fetches the frozen tokens from a collection
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `collection` - The Terra collection's address
+/// * `start_after` - The token_id of the last token in the previous query. Used in pagination.
+/// * `limit` - The maximum number of tokens to fetch. Used in pagination
+pub fn query_frozen_tokens(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let coll_addr = deps.api.addr_validate(&collection)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::Exclusive(s.into_bytes()));
+
+    let tokens = FROZEN_TOKENS
+        .prefix(coll_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (key, frozen) = pair?;
+            let token_id = String::from_utf8(key).map_err(StdError::from)?;
+            Ok(FrozenTokenResponse::new(token_id, frozen))
+        })
+        .collect::<Result<Vec<FrozenTokenResponse>, ContractError>>()?;
+
+    Ok(to_binary(&FrozenTokensResponse { tokens })?)
+}
+
+/// Fetches the releases waiting in the time-lock queue, oldest first
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `start_after` - The id of the last queued release in the previous query. Used in pagination.
+/// * `limit` - The maximum number of releases to fetch. Used in pagination
+pub fn query_queued_releases(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::Exclusive(s.to_be_bytes().into()));
+
+    let releases = QUEUED_RELEASES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (key, release) = pair?;
+            let queue_id = u64::from_be_bytes(
+                key.as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid queue id"))?,
+            );
+            Ok(QueuedReleaseResponse::new(queue_id, release))
+        })
+        .collect::<Result<Vec<QueuedReleaseResponse>, ContractError>>()?;
+
+    Ok(to_binary(&QueuedReleasesResponse { releases })?)
+}
+
+/// Fetches the NFTs admins have rescued, newest first
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `start_after` - The id of the last rescue in the previous query. Used in pagination.
+/// * `limit` - The maximum number of rescues to fetch. Used in pagination
+pub fn query_rescues(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_after.map(|s| Bound::Exclusive(s.to_be_bytes().into()));
+
+    let rescues = RESCUES
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|pair| {
+            let (key, record) = pair?;
+            let rescue_id = u64::from_be_bytes(
+                key.as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid rescue id"))?,
+            );
+            Ok(RescueResponse::new(rescue_id, record))
+        })
+        .collect::<Result<Vec<RescueResponse>, ContractError>>()?;
+
+    Ok(to_binary(&RescuesResponse { rescues })?)
+}
+
+/// Fetches the native coins held by the bridge
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+pub fn query_balances(deps: Deps, env: Env) -> Result<Binary, ContractError> {
+    let balances = deps.querier.query_all_balances(env.contract.address)?;
+    Ok(to_binary(&BalancesResponse { balances })?)
+}
+
+/// Fetches the operators' bonds and the bond configuration
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `start_after` - The address of the last operator in the previous query. Used in pagination.
+/// * `limit` - The maximum number of bonds to fetch. Used in pagination
+pub fn query_bonds(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|s| -> StdResult<_> { Ok(Bound::Exclusive(deps.api.addr_canonicalize(&s)?.to_vec())) })
+        .transpose()?;
+    let operators = OPERS.load(deps.storage)?;
+
+    let bonds = BONDS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (key, bond) = pair?;
+            let operator_raw = CanonicalAddr::from(key);
+            Ok(BondResponse {
+                operator: deps.api.addr_humanize(&operator_raw)?.into_string(),
+                is_active: operators.contains(&operator_raw)
+                    && check_is_bonded(deps.storage, &operator_raw)?,
+                slash_proposal: SLASH_PROPOSALS.may_load(deps.storage, operator_raw.to_vec())?,
+                bond,
+            })
+        })
+        .collect::<Result<Vec<BondResponse>, ContractError>>()?;
+
+    Ok(to_binary(&BondsResponse {
+        config: BOND_CONFIG.may_load(deps.storage)?,
+        bonds,
+    })?)
+}
+
+/// Fetches the funds admins have withdrawn, newest first
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `start_after` - The id of the last withdrawal in the previous query. Used in pagination.
+/// * `limit` - The maximum number of withdrawals to fetch. Used in pagination
+pub fn query_withdrawals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_after.map(|s| Bound::Exclusive(s.to_be_bytes().into()));
+
+    let withdrawals = WITHDRAWALS
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|pair| {
+            let (key, record) = pair?;
+            let withdrawal_id = u64::from_be_bytes(
+                key.as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid withdrawal id"))?,
+            );
+            Ok(WithdrawalResponse::new(withdrawal_id, record))
+        })
+        .collect::<Result<Vec<WithdrawalResponse>, ContractError>>()?;
+
+    Ok(to_binary(&WithdrawalsResponse { withdrawals })?)
+}
+
+/// Fetches the links of the hash chain over the bridge history, oldest first
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `start_after` - optional sequence number of the last link from the previous query
+/// * `limit` - optional max number of links to show
+pub fn query_history_links(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::Exclusive(s.to_be_bytes().into()));
+
+    let links = HISTORY_LINKS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (key, link) = pair?;
+            let seq = u64::from_be_bytes(
+                key.as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid chain sequence number"))?,
+            );
+            Ok(HistoryLinkResponse::new(seq, link))
+        })
+        .collect::<Result<Vec<HistoryLinkResponse>, ContractError>>()?;
+
+    Ok(to_binary(&HistoryLinksResponse { links })?)
+}
+
+/// Fetches the head of the hash chain over every write to the bridge history
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+pub fn query_history_chain(deps: Deps) -> Result<Binary, ContractError> {
+    let chain = HISTORY_CHAIN.may_load(deps.storage)?;
+
+    Ok(to_binary(&HistoryChainResponse {
+        seq: chain.as_ref().map_or(0, |chain| chain.seq),
+        head: chain.map(|chain| chain.head),
+    })?)
+}
+
+/// Fetches the last SN event processed by any operator, and by a given operator
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `operator` - Optional operator to fetch the checkpoint for
+pub fn query_checkpoint(deps: Deps, operator: Option<String>) -> Result<Binary, ContractError> {
+    let operator = match operator {
+        Some(operator) => {
+            let operator = deps.api.addr_validate(&operator)?;
+            CHECKPOINTS.may_load(deps.storage, operator)?
+        }
+        None => None,
+    };
+
+    Ok(to_binary(&CheckpointResponse {
+        global: CHECKPOINT.may_load(deps.storage)?,
+        operator,
+    })?)
+}
+
+/// Fetches the operators' latest heartbeats and whether the bridge is receiving tokens
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `start_after` - The address of the last operator in the previous query. Used in pagination.
+/// * `limit` - The maximum number of heartbeats to fetch. Used in pagination
+pub fn query_heartbeats(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::Exclusive(s.into_bytes()));
+
+    let heartbeats = HEARTBEATS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (key, heartbeat) = pair?;
+            Ok(HeartbeatResponse {
+                operator: String::from_utf8(key).map_err(StdError::from)?,
+                heartbeat,
+            })
+        })
+        .collect::<Result<Vec<HeartbeatResponse>, ContractError>>()?;
+
+    Ok(to_binary(&HeartbeatsResponse {
+        liveness_window: LIVENESS_WINDOW.may_load(deps.storage)?,
+        is_live: check_is_live(deps.storage, &env)?,
+        heartbeats,
+    })?)
+}
+
+/// Fetches whether the bridge has been wound down and the number of tokens that remain in escrow
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+pub fn query_wind_down_status(deps: Deps) -> Result<Binary, ContractError> {
+    let collections = ESCROW_COUNT
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|pair| {
+            let (key, remaining) = pair?;
+            let collection = String::from_utf8(key).map_err(StdError::from)?;
+            Ok(RemainingTokensResponse {
+                collection,
+                remaining,
+            })
+        })
+        .collect::<Result<Vec<RemainingTokensResponse>, ContractError>>()?;
+
+    Ok(to_binary(&WindDownStatusResponse {
+        wound_down_at: WOUND_DOWN_AT.may_load(deps.storage)?,
+        total_remaining: collections.iter().map(|coll| coll.remaining).sum(),
+        collections,
+    })?)
+}
+
+/// Fetches the NFTs that `address` can claim
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `address` - The Terra address allowed to claim the NFTs
+/// * `start_after` - The collection and token_id of the last claim received in the previous query.
+///   Used in pagination.
+/// * `limit` - The maximum number of claims to fetch. Used in pagination
+pub fn query_claims(
+    deps: Deps,
+    address: String,
+    start_after: Option<TokenKey>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let recipient = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|key| -> StdResult<_> {
+            let collection = deps.api.addr_validate(&key.collection)?;
+            Ok(Bound::Exclusive((collection, key.token_id).joined_key()))
+        })
+        .transpose()?;
+
+    let claims = CLAIMS_BY_RECIPIENT
+        .sub_prefix(recipient)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (collection, token_id) = pair?.1;
+            let claim = CLAIMS.load(deps.storage, (collection.to_owned(), token_id.to_owned()))?;
+            Ok(ClaimResponse::new(collection, token_id, claim))
+        })
+        .collect::<Result<Vec<ClaimResponse>, ContractError>>()?;
+
+    Ok(to_binary(&ClaimsResponse { claims })?)
+}
+
+/// Fetches the releases from a collection whose transfers failed
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `collection` - The Terra collection's address
+/// * `start_after` - The token_id of the last release received in the previous query. Used in pagination.
+/// * `limit` - The maximum number of releases to fetch. Used in pagination
+pub fn query_failed_releases(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let coll_addr = deps.api.addr_validate(&collection)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::Exclusive(s.into_bytes()));
+
+    let releases = FAILED_RELEASES
+        .prefix(coll_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (key, failed) = pair?;
+            let token_id = String::from_utf8(key).map_err(StdError::from)?;
+            Ok(FailedReleaseResponse::new(token_id, failed))
+        })
+        .collect::<Result<Vec<FailedReleaseResponse>, ContractError>>()?;
+
+    Ok(to_binary(&FailedReleasesResponse { releases })?)
+}
+
+/// Fetches the metadata snapshot taken the last time a token was received
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `collection` - The Terra collection's address
+/// * `token_id` - ID of the token to fetch the metadata for
+pub fn query_token_metadata(
+    deps: Deps,
+    collection: String,
+    token_id: String,
+) -> Result<Binary, ContractError> {
+    let coll_addr = deps.api.addr_validate(&collection)?;
+    let snapshot = METADATA.load(deps.storage, (coll_addr, token_id))?;
+    Ok(to_binary(&TokenMetadataResponse::from(snapshot))?)
+}
+
+/// Compares the escrow recorded for a collection against the tokens the collection
+/// reports as owned by the bridge
+///
+/// # Arguments
+///
+/// * `deps` - Extern containing all the contract's external dependencies
+/// * `env` - Env of the contract's environment
+/// * `collection` - The Terra collection's address
+/// * `start_after` - The `last_token_id` returned by the previous query. Used in pagination.
+/// * `limit` - The maximum number of tokens to check. Used in pagination
+pub fn query_reconcile_escrow(
+    deps: Deps,
+    env: Env,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u8>,
+) -> Result<Binary, ContractError> {
+    let coll_addr = deps.api.addr_validate(&collection)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .to_owned()
+        .map(|s| Bound::Exclusive(s.into_bytes()));
+
+    // Fetch the next page of recorded escrow
+    let escrowed = ESCROW
+        .prefix(coll_addr.to_owned())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|pair| {
+            let (key, record) = pair?;
+            Ok((String::from_utf8(key)?, record))
+        })
+        .collect::<StdResult<Vec<(String, EscrowRecord)>>>()?;
+
+    // Fetch the next page of tokens the collection reports as owned by the bridge
+    let held: TokensResponse = deps.querier.query_wasm_smart(
+        coll_addr.to_owned(),
+        &Cw721QueryMsg::Tokens {
+            owner: env.contract.address.to_string(),
+            start_after,
+            limit: Some(limit as u32),
+        },
+    )?;
+
+    // Only compare the token ids covered by both pages
+    let mut last_token_id: Option<String> = None;
+    if escrowed.len() == limit {
+        last_token_id = escrowed.last().map(|(token_id, _)| token_id.to_owned());
+    }
+    if held.tokens.len() == limit {
+        let last_held = held.tokens.last().cloned();
+        last_token_id = match last_token_id {
+            Some(last) => last_held.map(|held| held.min(last)),
+            None => last_held,
+        };
+    }
+    let in_page = |token_id: &String| last_token_id.as_ref().map_or(true, |last| token_id <= last);
+
+    let missing = escrowed
+        .into_iter()
+        .filter(|(token_id, _)| in_page(token_id))
+        .filter_map(|(token_id, record)| {
+            let owner =
+                query_owner_of(&deps.querier, coll_addr.to_owned(), token_id.to_owned()).ok();
+            if owner.as_deref() == Some(env.contract.address.as_str()) {
+                return None;
+            }
+            Some(MissingTokenResponse {
+                token_id,
+                depositor: record.depositor.into_string(),
+                owner,
+            })
+        })
+        .collect::<Vec<MissingTokenResponse>>();
+
+    let unexpected = held
+        .tokens
+        .into_iter()
+        .filter(|token_id| in_page(token_id))
+        .filter(|token_id| !ESCROW.has(deps.storage, (coll_addr.to_owned(), token_id.to_owned())))
+        .collect::<Vec<String>>();
+
+    Ok(to_binary(&ReconcileEscrowResponse {
+        missing,
+        unexpected,
+        last_token_id,
+    })?)
+}
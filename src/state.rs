@@ -1,14 +1,221 @@
-use cosmwasm_std::{Addr, CanonicalAddr, StdResult, Storage};
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    to_vec, Addr, Binary, CanonicalAddr, Coin, Order, StdError, StdResult, Storage, Uint128,
+};
 use cw_storage_plus::{Item, Map, U64Key};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::sha_256;
+
 /*
  *
  * Type Definitions
  *
  */
 
+/// The cw721 message used to deliver a released token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryMode {
+    /// Delivered to a wallet with `TransferNft`
+    Transfer,
+    /// Delivered to a contract with `SendNft`
+    Send,
+    /// Claimed by the recipient and delivered with `TransferNft`
+    Claim,
+}
+
+/// Where a bridging transaction is in its lifecycle
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeStatus {
+    /// Received by the bridge and awaiting acknowledgement from SN
+    Pending,
+    /// Acknowledged by SN, or released to the recipient
+    Completed,
+    /// Rejected by SN, or the transfer to the recipient failed
+    Failed,
+    /// Returned to the depositor
+    Refunded,
+}
+
+/// Sliding-window rate limits. Limits that are `None` are not enforced
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimits {
+    /// the length of the sliding window, in seconds
+    pub window: u64,
+    /// the maximum number of releases from a single collection per window
+    pub max_releases_per_collection: Option<u32>,
+    /// the maximum number of tokens a single Terra address can send to the bridge per window
+    pub max_receives_per_user: Option<u32>,
+    /// the maximum number of releases a single operator can make per window
+    pub max_releases_per_operator: Option<u32>,
+}
+
+/// What a rate limit is tracked for
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RateScope {
+    /// releases from a Terra collection
+    Collection,
+    /// tokens received from a Terra address
+    User,
+    /// releases made by an operator
+    Operator,
+}
+
+impl RateScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateScope::Collection => "collection",
+            RateScope::User => "user",
+            RateScope::Operator => "operator",
+        }
+    }
+
+    /// Returns the limit for this scope, if one is enforced
+    pub fn limit(&self, limits: &RateLimits) -> Option<u32> {
+        match self {
+            RateScope::Collection => limits.max_releases_per_collection,
+            RateScope::User => limits.max_receives_per_user,
+            RateScope::Operator => limits.max_releases_per_operator,
+        }
+    }
+}
+
+/// How operators bond funds to become active and how their bonds are slashed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondConfig {
+    /// the native denom bonds are paid in
+    pub denom: String,
+    /// the minimum bond an operator needs to be active
+    pub min_bond: Uint128,
+    /// the number of seconds unbonded funds are held before they can be claimed
+    pub unbonding_period: u64,
+    /// the address slashed funds are sent to
+    pub treasury: Addr,
+    /// the number of admins that must approve a slash
+    pub slash_approvals: u32,
+}
+
+/// Storage for the funds an operator has bonded
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Bond {
+    /// the bonded amount, locked while the address is an operator
+    pub amount: Uint128,
+    /// the amount waiting out the unbonding period
+    pub unbonding: Uint128,
+    /// the time (in seconds since 01/01/1970) the unbonding amount can be claimed
+    pub unbonded_at: Option<u64>,
+}
+
+impl Bond {
+    /// The bonded and unbonding funds, all of which can be slashed
+    pub fn total(&self) -> Uint128 {
+        self.amount + self.unbonding
+    }
+}
+
+/// Storage for a slash waiting for admin approvals
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SlashProposal {
+    /// the amount to slash
+    pub amount: Uint128,
+    /// the bad release that justifies the slash
+    pub reason: String,
+    /// the admins that approved the slash
+    pub approvals: Vec<Addr>,
+}
+
+/// A position in the SN event stream. Checkpoints are ordered by height, then event index
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+pub struct Checkpoint {
+    /// the SN block height
+    pub sn_height: u64,
+    /// the index of the event within the SN block
+    pub event_index: u32,
+}
+
+/// Storage for an operator's latest heartbeat
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Heartbeat {
+    /// the last SN block height the operator has seen
+    pub last_sn_height: u64,
+    /// the Terra block of the heartbeat
+    pub block_height: u64,
+    /// the time (in seconds since 01/01/1970) of the heartbeat
+    pub block_time: u64,
+}
+
+/// Conditions under which the bridge pauses itself
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CircuitBreaker {
+    /// the length of the window releases are counted over, in seconds
+    pub window: u64,
+    /// pauses a collection when more than this many of its tokens are released per window
+    pub max_releases_per_window: Option<u32>,
+    /// pauses a collection when a release targets a token missing from the escrow ledger
+    pub trip_on_unescrowed_release: bool,
+    /// pauses the bridge when an acknowledgement conflicts with an earlier one
+    pub trip_on_conflicting_acknowledgement: bool,
+}
+
+/// Why the circuit breaker paused the bridge or a collection
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TripReason {
+    /// more than `max` releases were attempted within `window` seconds
+    ReleaseThreshold { max: u32, window: u64 },
+    /// a release targeted a token that is not in the escrow ledger
+    UnescrowedRelease { token_id: String },
+    /// an acknowledgement for `seq` conflicted with an earlier one
+    ConflictingAcknowledgement { seq: u64 },
+}
+
+impl TripReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TripReason::ReleaseThreshold { .. } => "release_threshold",
+            TripReason::UnescrowedRelease { .. } => "unescrowed_release",
+            TripReason::ConflictingAcknowledgement { .. } => "conflicting_acknowledgement",
+        }
+    }
+}
+
+/// Storage for a pause made by the circuit breaker
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Trip {
+    /// why the circuit breaker tripped
+    pub reason: TripReason,
+    /// the time (in seconds since 01/01/1970) the circuit breaker tripped
+    pub tripped_at: u64,
+}
+
+/// Storage for the outcome SN reported for a received token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Acknowledgement {
+    /// true if SN accepted the token
+    pub success: bool,
+    /// the hash of the SN transaction that handled the token
+    pub sn_tx_hash: Option<String>,
+}
+
+impl Acknowledgement {
+    /// Returns `true` if the acknowledgements report different outcomes, or name different SN
+    /// transactions. A missing transaction hash never conflicts
+    pub fn conflicts_with(&self, other: &Acknowledgement) -> bool {
+        let hashes_differ = match (&self.sn_tx_hash, &other.sn_tx_hash) {
+            (Some(hash), Some(other_hash)) => hash != other_hash,
+            _ => false,
+        };
+        self.success != other.success || hashes_differ
+    }
+}
+
 /// Storage for the history of a tokens bridging activity
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct BridgeRecord {
@@ -28,11 +235,247 @@ pub struct BridgeRecord {
     pub block_height: u64,
     /// the time (in seconds since 01/01/1970) of tx
     pub block_time: u64,
+    /// the SHA-256 hash of the payload forwarded to a contract recipient on release
+    pub payload_hash: Option<Binary>,
+    /// the cw721 message used to deliver the token on release
+    pub delivery_mode: Option<DeliveryMode>,
+    /// the bridge-wide sequence number of a received token, used to acknowledge it
+    pub seq: Option<u64>,
+    /// the lifecycle status of the transaction
+    pub status: Option<BridgeStatus>,
+    /// the hash of the SN transaction that acknowledged the token
+    pub sn_tx_hash: Option<String>,
 }
 
 /// (contract_address, token_id, history_id)
 pub type HistoryPK = (Addr, String, U64Key);
 
+/// Storage for the head of the hash chain over every write to the bridge history
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HistoryChain {
+    /// the number of history writes chained so far
+    pub seq: u64,
+    /// SHA-256 of the previous head followed by the JSON encoding of the latest [HistoryLink].
+    /// The first link is hashed on its own
+    pub head: Binary,
+}
+
+/// A write to the bridge history, as encoded into the [HistoryChain]. The canonical encoding
+/// is the compact JSON serialization of this struct, with fields in declaration order, and
+/// `None` values written as `null`. SN verifiers must hash exactly these bytes
+#[derive(Serialize, Debug)]
+pub struct HistoryLink<'a> {
+    /// the address of the Terra collection
+    pub collection: &'a Addr,
+    /// id of bridged token
+    pub token_id: &'a str,
+    /// the id of the record in the token's history
+    pub history_id: u64,
+    /// the record as written
+    pub record: &'a BridgeRecord,
+}
+
+/// Storage for a link of the [HistoryChain], so the chain can be replayed after the
+/// record it chained has been overwritten
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HistoryLinkRecord {
+    /// the address of the Terra collection
+    pub collection: Addr,
+    /// id of bridged token
+    pub token_id: String,
+    /// the id of the record in the token's history
+    pub history_id: u64,
+    /// the record as written
+    pub record: BridgeRecord,
+    /// the head of the chain once this link was added
+    pub head: Binary,
+}
+
+/// Storage for a token that is currently held in escrow by the bridge
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EscrowRecord {
+    /// the Terra address that sent the token to the bridge
+    pub depositor: Addr,
+    /// the SN address that the token was bridged to
+    pub destination_address: String,
+    /// the Terra block the token was received in
+    pub block_height: u64,
+}
+
+/// (contract_address, token_id, history_id) of a BridgeRecord awaiting acknowledgement
+pub type PendingBridgePK = (Addr, String, u64);
+
+/// Storage for an operator's hold on reclaiming a received token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReclaimHold {
+    /// the time (in seconds since 01/01/1970) before which the token can not be reclaimed.
+    /// * `None` blocks reclaiming until the hold is replaced or the token is acknowledged
+    pub until: Option<u64>,
+}
+
+/// (contract_address, token_id)
+pub type EscrowPK = (Addr, String);
+
+/// Storage for a token that an operator has made claimable by a Terra address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Claim {
+    /// the Terra address allowed to claim the token
+    pub recipient: Addr,
+    /// the address of the SN collection
+    pub sn_collection: String,
+    /// the SN address that initiated the release
+    pub sn_address: String,
+    /// the time (in seconds since 01/01/1970) after which the token can no longer be claimed
+    pub expires_at: Option<u64>,
+}
+
+/// Storage for a release from a time-locked collection that is waiting to unlock
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedRelease {
+    /// the address of the Terra collection
+    pub collection: Addr,
+    /// id of the queued token
+    pub token_id: String,
+    /// the Terra address receiving the token
+    pub recipient: Addr,
+    /// the address of the SN collection
+    pub sn_collection: String,
+    /// the SN address that initiated the release
+    pub sn_address: String,
+    /// how the token will be delivered
+    pub delivery_mode: DeliveryMode,
+    /// optional message forwarded to a contract recipient
+    pub payload: Option<Binary>,
+    /// history id of the inbound BridgeRecord of the deposit being released, if there was one
+    pub deposit_history_id: Option<u64>,
+    /// the time (in seconds since 01/01/1970) the release was queued
+    pub queued_at: u64,
+    /// the time (in seconds since 01/01/1970) from which the release can be executed
+    pub unlock_at: u64,
+    /// the watcher's challenge, if the release was challenged
+    pub challenge: Option<Challenge>,
+}
+
+/// Storage for a watcher's veto of a queued release
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Challenge {
+    /// the watcher that challenged the release
+    pub watcher: Addr,
+    /// why the watcher challenged the release
+    pub reason: String,
+    /// the time (in seconds since 01/01/1970) of the challenge
+    pub challenged_at: u64,
+}
+
+/// Storage for a release whose transfer was dispatched in the current message
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRelease {
+    /// the address of the Terra collection
+    pub collection: Addr,
+    /// id of the released token
+    pub token_id: String,
+    /// the Terra address receiving the token
+    pub recipient: Addr,
+    /// the address of the SN collection
+    pub sn_collection: String,
+    /// the SN address that initiated the release
+    pub sn_address: String,
+    /// the id of the BridgeRecord saved for the release
+    pub history_id: u64,
+    /// the escrow record removed when the release was dispatched
+    pub escrow: Option<EscrowRecord>,
+}
+
+/// Storage for a release whose transfer failed. The token remains in escrow.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FailedRelease {
+    /// the Terra address that should have received the token
+    pub recipient: Addr,
+    /// the address of the SN collection
+    pub sn_collection: String,
+    /// the SN address that initiated the release
+    pub sn_address: String,
+    /// the id of the BridgeRecord saved for the release
+    pub history_id: u64,
+    /// the error returned by the Terra collection
+    pub error: String,
+    /// the Terra block the release failed in
+    pub block_height: u64,
+}
+
+/// Storage for an NFT an admin returned after it was transferred to the bridge without `SendNft`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RescueRecord {
+    /// the address of the Terra collection
+    pub collection: Addr,
+    /// id of the rescued token
+    pub token_id: String,
+    /// the Terra address the token was returned to
+    pub recipient: Addr,
+    /// the admin that rescued the token
+    pub admin: Addr,
+    /// the Terra block of the tx
+    pub block_height: u64,
+    /// the time (in seconds since 01/01/1970) of tx
+    pub block_time: u64,
+}
+
+/// Funds withdrawn from the bridge
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawnFunds {
+    /// native coins sent with `BankMsg::Send`
+    Native(Vec<Coin>),
+    /// cw20 tokens sent with `Cw20ExecuteMsg::Transfer`
+    Cw20 { token: Addr, amount: Uint128 },
+}
+
+/// Storage for funds an admin withdrew from the bridge
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalRecord {
+    /// the withdrawn funds
+    pub funds: WithdrawnFunds,
+    /// the Terra address the funds were sent to
+    pub recipient: Addr,
+    /// the admin that withdrew the funds
+    pub admin: Addr,
+    /// the Terra block of the tx
+    pub block_height: u64,
+    /// the time (in seconds since 01/01/1970) of tx
+    pub block_time: u64,
+}
+
+/// Storage for a token an admin froze while a dispute is resolved
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FrozenToken {
+    /// the dispute note attached by the admin
+    pub note: String,
+    /// the admin that froze the token
+    pub admin: Addr,
+    /// the time (in seconds since 01/01/1970) the token was frozen
+    pub frozen_at: u64,
+}
+
+/// Storage for a Terra collection's cw721 contract info, recorded when its mapping was added
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionInfo {
+    /// the collection's name
+    pub name: String,
+    /// the collection's symbol
+    pub symbol: String,
+}
+
+/// Storage for a token's metadata as reported by its collection when the token was received
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MetadataSnapshot {
+    /// the raw cw721 `NftInfo` response returned by the Terra collection
+    pub nft_info: Binary,
+    /// the SHA-256 hash of `nft_info`
+    pub hash: Binary,
+    /// the id of the BridgeRecord saved when the snapshot was taken
+    pub history_id: u64,
+}
+
 /*
  *
  * Contract State
@@ -41,11 +484,18 @@ pub type HistoryPK = (Addr, String, U64Key);
 
 pub const DEFAULT_LIMIT: u8 = 15;
 pub const MAX_LIMIT: u8 = 30;
+/// Default number of seconds after which an unacknowledged token can be reclaimed by its depositor
+pub const DEFAULT_RECLAIM_TIMEOUT: u64 = 7 * 24 * 60 * 60;
+/// Maximum size (in bytes) of the payload forwarded to contract recipients on release
+pub const MAX_PAYLOAD_SIZE: usize = 4096;
 
 /*
  * Storage
  */
 
+/// The time (in seconds since 01/01/1970) the bridge was wound down.
+/// Once set, the bridge no longer receives tokens and depositors can withdraw their escrowed tokens
+pub const WOUND_DOWN_AT: Item<u64> = Item::new("wound_down_at");
 /// Boolean value that determines whether the bridge can receive and release tokens
 pub const IS_PAUSED: Item<bool> = Item::new("is_paused");
 /// Mapping of a Terra collection's address to a bool determining if the NFTs
@@ -55,15 +505,121 @@ pub const IS_COLL_PAUSED: Map<Addr, bool> = Map::new("is_coll_paused");
 pub const ADMINS: Item<Vec<CanonicalAddr>> = Item::new("admins");
 /// Vector of operators' raw addresses
 pub const OPERS: Item<Vec<CanonicalAddr>> = Item::new("operators");
+/// How operators bond funds and how their bonds are slashed
+pub const BOND_CONFIG: Item<BondConfig> = Item::new("bond_config");
+/// Mapping of an operator's raw address to the operator's Bond
+pub const BONDS: Map<Vec<u8>, Bond> = Map::new("bonds");
+/// The sum of all bonded and unbonding funds
+pub const TOTAL_BONDED: Item<Uint128> = Item::new("total_bonded");
+/// Mapping of an operator's raw address to the SlashProposal against the operator
+pub const SLASH_PROPOSALS: Map<Vec<u8>, SlashProposal> = Map::new("slash_proposals");
+/// Vector of guardians' raw addresses. Guardians can cancel queued releases
+pub const GUARDIANS: Item<Vec<CanonicalAddr>> = Item::new("guardians");
+/// Vector of watchers' raw addresses. Watchers can challenge queued releases
+pub const WATCHERS: Item<Vec<CanonicalAddr>> = Item::new("watchers");
+/// Mapping of a Terra collection's address to the number of seconds its releases are queued for
+pub const RELEASE_DELAYS: Map<Addr, u64> = Map::new("release_delays");
+/// The number of seconds every release is queued for so watchers can challenge it.
+/// Applies to every collection, on top of any collection's RELEASE_DELAYS
+pub const CHALLENGE_PERIOD: Item<u64> = Item::new("challenge_period");
+/// The id of the last queued release
+pub const QUEUED_RELEASE_SEQ: Item<u64> = Item::new("queued_release_seq");
+/// Mapping of a queue id to the QueuedRelease waiting to unlock
+pub const QUEUED_RELEASES: Map<U64Key, QueuedRelease> = Map::new("queued_releases");
+/// Mapping of a Terra contract and token id to the queue id of the token's queued release
+pub const QUEUED_TOKENS: Map<EscrowPK, u64> = Map::new("queued_tokens");
+/// Mapping of an operator's address to the last SN event the operator fully processed
+pub const CHECKPOINTS: Map<Addr, Checkpoint> = Map::new("checkpoints");
+/// The last SN event fully processed by any operator
+pub const CHECKPOINT: Item<Checkpoint> = Item::new("checkpoint");
+/// Mapping of an operator's address to the operator's latest Heartbeat
+pub const HEARTBEATS: Map<Addr, Heartbeat> = Map::new("heartbeats");
+/// The time (in seconds since 01/01/1970) of the latest heartbeat from any operator
+pub const LAST_HEARTBEAT: Item<u64> = Item::new("last_heartbeat");
+/// The number of seconds since the latest heartbeat after which the bridge stops receiving tokens
+pub const LIVENESS_WINDOW: Item<u64> = Item::new("liveness_window");
+/// The conditions under which the bridge pauses itself
+pub const CIRCUIT_BREAKER: Item<CircuitBreaker> = Item::new("circuit_breaker");
+/// The reason the circuit breaker paused the whole bridge
+pub const BRIDGE_TRIP: Item<Trip> = Item::new("bridge_trip");
+/// Mapping of a Terra collection's address to the reason the circuit breaker paused it
+pub const COLL_TRIPS: Map<Addr, Trip> = Map::new("coll_trips");
+/// Mapping of a Terra collection's address to the times (in seconds since 01/01/1970)
+/// of its releases in the circuit breaker's current window
+pub const BREAKER_USAGE: Map<Addr, Vec<u64>> = Map::new("breaker_usage");
 /// Mapping of a Terra collection's address to a Secret Network collection's address
 pub const TERRA_TO_SN_MAP: Map<Addr, String> = Map::new("t_to_s");
 /// Mapping of a Terra collection's address to a Secret Network collection's address.
 /// Used for reverse look ups
 pub const SN_TO_TERRA_MAP: Map<String, Addr> = Map::new("s_to_t");
+/// Mapping of a Terra collection's address to a bool determining if only allowlisted
+/// addresses can bridge its NFTs
+pub const ALLOWLIST_ONLY: Map<Addr, bool> = Map::new("allowlist_only");
+/// Mapping of a Terra collection's address and a Terra address to a bool determining if the
+/// address can bridge the collection's NFTs in allowlist-only mode
+pub const ALLOWLIST: Map<(Addr, Addr), bool> = Map::new("allowlist");
+/// The sliding-window rate limits enforced on receives and releases
+pub const RATE_LIMITS: Item<RateLimits> = Item::new("rate_limits");
+/// Mapping of a [RateScope] and subject address to the times (in seconds since 01/01/1970)
+/// of the subject's activity in the current window
+pub const RATE_USAGE: Map<(String, String), Vec<u64>> = Map::new("rate_usage");
+/// Mapping of a Terra collection's address to the CollectionInfo recorded when it was mapped
+pub const COLLECTION_INFO: Map<Addr, CollectionInfo> = Map::new("coll_info");
 /// Mapping of a Terra contract and token id to the number of TX records for that pair
 pub const HISTORY_COUNT: Map<(Addr, String), u64> = Map::new("history_pk");
 /// Mapping of a Terra contract, token id, and TX record id to the BridgeRecord for that TX
 pub const HISTORY: Map<HistoryPK, BridgeRecord> = Map::new("history");
+/// The head of the hash chain over every write to HISTORY
+pub const HISTORY_CHAIN: Item<HistoryChain> = Item::new("history_chain");
+/// Mapping of a chain sequence number to the HistoryLinkRecord of that write to HISTORY
+pub const HISTORY_LINKS: Map<U64Key, HistoryLinkRecord> = Map::new("history_links");
+/// The sequence number of the last token received by the bridge
+pub const BRIDGE_SEQ: Item<u64> = Item::new("bridge_seq");
+/// Mapping of a sequence number to the [PendingBridgePK] of a received token that SN
+/// has not acknowledged yet
+pub const PENDING_BRIDGES: Map<U64Key, PendingBridgePK> = Map::new("pending_bridges");
+/// Mapping of a sequence number to the Acknowledgement an operator made for a received token
+pub const ACKNOWLEDGEMENTS: Map<U64Key, Acknowledgement> = Map::new("acknowledgements");
+/// Mapping of a sequence number to the ReclaimHold an operator placed on a pending token
+pub const RECLAIM_HOLDS: Map<U64Key, ReclaimHold> = Map::new("reclaim_holds");
+/// Number of seconds after which an unacknowledged token can be reclaimed by its depositor
+pub const RECLAIM_TIMEOUT: Item<u64> = Item::new("reclaim_timeout");
+/// Mapping of a Terra contract and token id to the EscrowRecord for tokens held by the bridge
+pub const ESCROW: Map<EscrowPK, EscrowRecord> = Map::new("escrow");
+/// Mapping of a Terra contract and token id to the Claim for tokens awaiting a pull-based release
+pub const CLAIMS: Map<EscrowPK, Claim> = Map::new("claims");
+/// Mapping of a recipient, Terra contract, and token id to the [EscrowPK] of the token.
+/// Used to look up the claims for a single recipient
+pub const CLAIMS_BY_RECIPIENT: Map<(Addr, Addr, String), EscrowPK> = Map::new("claims_by_rcpt");
+/// The reply id of the last dispatched release
+pub const RELEASE_REPLY_SEQ: Item<u64> = Item::new("release_reply_seq");
+/// Mapping of a reply id to the PendingRelease whose transfer was dispatched with it.
+/// Each entry is removed by its own reply
+pub const PENDING_RELEASES: Map<U64Key, PendingRelease> = Map::new("pending_releases");
+/// Mapping of a Terra contract and token id to the FailedRelease for tokens that could not be delivered
+pub const FAILED_RELEASES: Map<EscrowPK, FailedRelease> = Map::new("failed_releases");
+/// Mapping of a Terra or SN address to a bool determining if the address can not use the bridge
+pub const DENYLIST: Map<String, bool> = Map::new("denylist");
+/// An external contract that answers [crate::msg::DenylistQueryMsg] queries
+pub const DENYLIST_CONTRACT: Item<Addr> = Item::new("denylist_contract");
+/// Mapping of a Terra contract and token id to the FrozenToken for tokens that can not be
+/// received or released
+pub const FROZEN_TOKENS: Map<EscrowPK, FrozenToken> = Map::new("frozen_tokens");
+/// Mapping of a Terra contract and token id to the token's most recent MetadataSnapshot
+pub const METADATA: Map<EscrowPK, MetadataSnapshot> = Map::new("metadata");
+/// The number of RescueRecords saved
+pub const RESCUE_COUNT: Item<u64> = Item::new("rescue_count");
+/// Mapping of a rescue id to the RescueRecord for that rescue
+pub const RESCUES: Map<U64Key, RescueRecord> = Map::new("rescues");
+/// The number of WithdrawalRecords saved
+pub const WITHDRAWAL_COUNT: Item<u64> = Item::new("withdrawal_count");
+/// Mapping of a withdrawal id to the WithdrawalRecord for that withdrawal
+pub const WITHDRAWALS: Map<U64Key, WithdrawalRecord> = Map::new("withdrawals");
+/// Mapping of a Terra contract to the number of its tokens held in escrow
+pub const ESCROW_COUNT: Map<Addr, u64> = Map::new("escrow_count");
+/// Mapping of a depositor, Terra contract, and token id to the [EscrowPK] of the token.
+/// Used to look up the tokens escrowed by a single depositor
+pub const ESCROW_BY_DEPOSITOR: Map<(Addr, Addr, String), EscrowPK> = Map::new("escrow_by_dep");
 
 pub fn next_history_pk(
     store: &mut dyn Storage,
@@ -87,11 +643,214 @@ pub fn save_history(
 ) -> StdResult<u64> {
     let history_id: u64 =
         next_history_pk(store, source_collection.to_owned(), token_id.to_owned())?;
+    write_history(store, &source_collection, &token_id, history_id, &record)?;
+    // Return history_id to be used in wasm attributes
+    Ok(history_id)
+}
+
+/// Saves a BridgeRecord and chains the write into the HISTORY_CHAIN, keeping the link in
+/// HISTORY_LINKS. All writes to HISTORY must go through here so the chain covers status
+/// changes as well as new records
+pub fn write_history(
+    store: &mut dyn Storage,
+    collection: &Addr,
+    token_id: &str,
+    history_id: u64,
+    record: &BridgeRecord,
+) -> StdResult<HistoryChain> {
+    let link = to_vec(&HistoryLink {
+        collection,
+        token_id,
+        history_id,
+        record,
+    })?;
+    let (seq, mut preimage) = match HISTORY_CHAIN.may_load(store)? {
+        Some(chain) => (chain.seq, chain.head.to_vec()),
+        None => (0, vec![]),
+    };
+    preimage.extend(link);
+    let chain = HistoryChain {
+        seq: seq + 1,
+        head: sha_256(&preimage),
+    };
+    HISTORY_CHAIN.save(store, &chain)?;
+    HISTORY_LINKS.save(
+        store,
+        chain.seq.into(),
+        &HistoryLinkRecord {
+            collection: collection.to_owned(),
+            token_id: token_id.to_owned(),
+            history_id,
+            record: record.to_owned(),
+            head: chain.head.to_owned(),
+        },
+    )?;
     HISTORY.save(
         store,
-        (source_collection, token_id, history_id.into()),
-        &record,
+        (
+            collection.to_owned(),
+            token_id.to_owned(),
+            history_id.into(),
+        ),
+        record,
     )?;
-    // Return history_id to be used in wasm attributes
-    Ok(history_id)
+    Ok(chain)
+}
+
+/// Loads the token's most recent BridgeRecord and its history id
+pub fn latest_history(
+    store: &dyn Storage,
+    source_collection: Addr,
+    token_id: String,
+) -> StdResult<Option<(u64, BridgeRecord)>> {
+    let key = (source_collection, token_id);
+    match HISTORY_COUNT.may_load(store, key.clone())? {
+        Some(history_id) => {
+            let record = HISTORY.load(store, (key.0, key.1, history_id.into()))?;
+            Ok(Some((history_id, record)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Loads the token's most recent inbound BridgeRecord and its history id
+pub fn latest_inbound_history(
+    store: &dyn Storage,
+    source_collection: Addr,
+    token_id: String,
+) -> StdResult<Option<(u64, BridgeRecord)>> {
+    for pair in
+        HISTORY
+            .prefix((source_collection, token_id))
+            .range(store, None, None, Order::Descending)
+    {
+        let (key, record) = pair?;
+        if record.is_enter {
+            let history_id = u64::from_be_bytes(
+                key.as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid history id"))?,
+            );
+            return Ok(Some((history_id, record)));
+        }
+    }
+    Ok(None)
+}
+
+pub fn next_bridge_seq(store: &mut dyn Storage) -> StdResult<u64> {
+    let seq = BRIDGE_SEQ.may_load(store)?.unwrap_or(0u64) + 1;
+    BRIDGE_SEQ.save(store, &seq)?;
+    Ok(seq)
+}
+
+pub fn save_rescue(store: &mut dyn Storage, record: RescueRecord) -> StdResult<u64> {
+    let rescue_id = RESCUE_COUNT.may_load(store)?.unwrap_or(0u64) + 1;
+    RESCUE_COUNT.save(store, &rescue_id)?;
+    RESCUES.save(store, rescue_id.into(), &record)?;
+    Ok(rescue_id)
+}
+
+pub fn save_withdrawal(store: &mut dyn Storage, record: WithdrawalRecord) -> StdResult<u64> {
+    let withdrawal_id = WITHDRAWAL_COUNT.may_load(store)?.unwrap_or(0u64) + 1;
+    WITHDRAWAL_COUNT.save(store, &withdrawal_id)?;
+    WITHDRAWALS.save(store, withdrawal_id.into(), &record)?;
+    Ok(withdrawal_id)
+}
+
+/// Saves a dispatched release under a new reply id, which is returned for its SubMsg
+pub fn save_pending_release(store: &mut dyn Storage, pending: &PendingRelease) -> StdResult<u64> {
+    let reply_id = RELEASE_REPLY_SEQ.may_load(store)?.unwrap_or(0u64) + 1;
+    RELEASE_REPLY_SEQ.save(store, &reply_id)?;
+    PENDING_RELEASES.save(store, reply_id.into(), pending)?;
+    Ok(reply_id)
+}
+
+pub fn save_queued_release(store: &mut dyn Storage, release: QueuedRelease) -> StdResult<u64> {
+    let queue_id = QUEUED_RELEASE_SEQ.may_load(store)?.unwrap_or(0u64) + 1;
+    QUEUED_RELEASE_SEQ.save(store, &queue_id)?;
+    QUEUED_TOKENS.save(
+        store,
+        (release.collection.to_owned(), release.token_id.to_owned()),
+        &queue_id,
+    )?;
+    QUEUED_RELEASES.save(store, queue_id.into(), &release)?;
+    Ok(queue_id)
+}
+
+pub fn remove_queued_release(store: &mut dyn Storage, queue_id: u64, release: &QueuedRelease) {
+    QUEUED_TOKENS.remove(
+        store,
+        (release.collection.to_owned(), release.token_id.to_owned()),
+    );
+    QUEUED_RELEASES.remove(store, queue_id.into());
+}
+
+/// Returns the times of `subject`'s activity within the last `window` seconds
+pub fn window_usage(
+    store: &dyn Storage,
+    scope: RateScope,
+    subject: &str,
+    window: u64,
+    now: u64,
+) -> StdResult<Vec<u64>> {
+    let mut usage = RATE_USAGE
+        .may_load(store, (scope.as_str().to_owned(), subject.to_owned()))?
+        .unwrap_or_default();
+    usage.retain(|time| time + window > now);
+    Ok(usage)
+}
+
+pub fn save_escrow(
+    store: &mut dyn Storage,
+    source_collection: Addr,
+    token_id: String,
+    record: EscrowRecord,
+) -> StdResult<()> {
+    let key = (source_collection.to_owned(), token_id.to_owned());
+    match ESCROW.may_load(store, key.clone())? {
+        // Drop the old depositor's index entry so the token is only listed under the new one
+        Some(previous) if previous.depositor != record.depositor => {
+            ESCROW_BY_DEPOSITOR.remove(
+                store,
+                (previous.depositor.to_owned(), key.0.clone(), key.1.clone()),
+            );
+        }
+        Some(_) => {}
+        None => {
+            ESCROW_COUNT.update(store, key.0.clone(), |count| -> StdResult<u64> {
+                Ok(count.unwrap_or(0) + 1)
+            })?;
+        }
+    }
+    ESCROW_BY_DEPOSITOR.save(
+        store,
+        (
+            record.depositor.to_owned(),
+            source_collection.to_owned(),
+            token_id.to_owned(),
+        ),
+        &(source_collection, token_id),
+    )?;
+    ESCROW.save(store, key, &record)
+}
+
+pub fn remove_escrow(
+    store: &mut dyn Storage,
+    source_collection: Addr,
+    token_id: String,
+) -> StdResult<Option<EscrowRecord>> {
+    let key = (source_collection, token_id);
+    let record = ESCROW.may_load(store, key.clone())?;
+    if let Some(record) = &record {
+        ESCROW_BY_DEPOSITOR.remove(
+            store,
+            (record.depositor.to_owned(), key.0.clone(), key.1.clone()),
+        );
+        match ESCROW_COUNT.may_load(store, key.0.clone())? {
+            Some(count) if count > 1 => ESCROW_COUNT.save(store, key.0.clone(), &(count - 1))?,
+            _ => ESCROW_COUNT.remove(store, key.0.clone()),
+        }
+        ESCROW.remove(store, key);
+    }
+    Ok(record)
 }
@@ -1,28 +1,79 @@
 #[cfg(test)]
 mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
     use cosmwasm_std::{
-        from_binary,
-        testing::{mock_dependencies, mock_env, mock_info},
-        to_binary, Addr, Api, DepsMut, Response, WasmMsg,
+        attr, coins, from_binary, from_slice,
+        testing::{
+            mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+            MOCK_CONTRACT_ADDR,
+        },
+        to_binary, to_vec, Addr, Api, BankMsg, Binary, ContractResult, Deps, DepsMut, Empty, Env,
+        Event, MessageInfo, OwnedDeps, Querier, QuerierResult, QueryRequest, Reply, Response,
+        StdError, SubMsg, SubMsgExecutionResponse, SystemError, SystemResult, Uint128, WasmMsg,
+        WasmQuery,
+    };
+    use cw20::Cw20ExecuteMsg;
+    use cw721::{
+        ContractInfoResponse, Cw721ExecuteMsg, Cw721QueryMsg, NftInfoResponse, NumTokensResponse,
+        OwnerOfResponse, TokensResponse,
+    };
+    use sha2::{Digest, Sha256};
+    use terra_cosmwasm::{
+        ContractInfoResponse as TerraContractInfoResponse, TerraQuery, TerraQueryWrapper,
     };
-    use cw721::Cw721ExecuteMsg;
 
     use crate::{
-        contract::instantiate,
+        contract::{instantiate, reply},
         error::ContractError,
         execute::{
-            try_receive_nft, try_release_nft, try_update_collection_mappings, try_update_pause,
-            try_update_super_users,
+            try_acknowledge_bridge, try_approve_slash, try_backfill_escrow, try_bond,
+            try_cancel_queued_release, try_challenge_release, try_checkpoint, try_claim_bond,
+            try_claim_nft, try_dismiss_challenge, try_execute_queued_release, try_freeze_token,
+            try_heartbeat, try_hold_reclaim, try_mark_claimable, try_propose_slash,
+            try_receive_nft, try_reclaim_nft, try_release_nft, try_release_nfts, try_rescue_nft,
+            try_resolve_dispute, try_retry_release, try_revoke_claim, try_unbond,
+            try_update_allowlist, try_update_bond_config, try_update_challenge_period,
+            try_update_circuit_breaker, try_update_collection_mappings, try_update_denylist,
+            try_update_guardians, try_update_liveness_window, try_update_pause,
+            try_update_rate_limits, try_update_reclaim_timeout, try_update_release_delay,
+            try_update_super_users, try_update_watchers, try_wind_down, try_withdraw_cw20,
+            try_withdraw_native, try_withdraw_nft,
         },
         msg::{
-            AdminsResponse, BridgeRecordResponse, CollectionMapping, CollectionMappingResponse,
-            HistoryResponse, InstantiateMsg, OperatorsResponse,
+            AdminsResponse, AllowlistResponse, BalancesResponse, BondConfigMsg, BondResponse,
+            BondsResponse, BridgeRecordResponse, CheckpointResponse, ClaimResponse, ClaimsResponse,
+            CollectionMapping, CollectionMappingResponse, DenylistQueryMsg, DenylistResponse,
+            EscrowedTokenResponse, EscrowedTokensResponse, FailedReleaseResponse,
+            FailedReleasesResponse, FrozenTokenResponse, FrozenTokensResponse, GuardiansResponse,
+            HeartbeatResponse, HeartbeatsResponse, HistoryChainResponse, HistoryLinksResponse,
+            HistoryResponse, InstantiateMsg, IsDeniedResponse, MissingTokenResponse,
+            OperatorsResponse, PauseStatusResponse, PendingBridgesResponse, QueuedReleaseResponse,
+            QueuedReleasesResponse, RateLimitUsageResponse, ReconcileEscrowResponse,
+            ReleaseNftsResponse, ReleaseRequest, RemainingTokensResponse, RescueResponse,
+            RescuesResponse, SkippedRelease, TokenKey, TokenMetadataResponse, WatchersResponse,
+            WindDownStatusResponse, WindowUsageResponse, WithdrawalResponse, WithdrawalsResponse,
+        },
+        query::{
+            query_admins, query_allowlist, query_balances, query_bonds, query_checkpoint,
+            query_claims, query_collection_mappings, query_denylist, query_escrowed_tokens,
+            query_escrowed_tokens_by_depositor, query_failed_releases, query_frozen_tokens,
+            query_guardians, query_heartbeats, query_history, query_history_chain,
+            query_history_links, query_operators, query_pause_status, query_pending_bridges,
+            query_queued_releases, query_rate_limit_usage, query_reconcile_escrow, query_rescues,
+            query_token_metadata, query_watchers, query_wind_down_status, query_withdrawals,
+        },
+        state::{
+            remove_escrow, save_escrow, Bond, BondConfig, BridgeStatus, Challenge, Checkpoint,
+            CircuitBreaker, CollectionInfo, DeliveryMode, EscrowRecord, Heartbeat, HistoryLink,
+            RateLimits, Trip, TripReason, WithdrawnFunds, ESCROW, HISTORY, MAX_PAYLOAD_SIZE,
+            PENDING_BRIDGES, PENDING_RELEASES,
         },
-        query::{query_admins, query_collection_mappings, query_history, query_operators},
     };
 
     // Static variables for testing
     const CREATOR: &str = "creator";
+    const DENYLIST_ADDR: &str = "terra denylist";
 
     fn get_admins() -> Vec<String> {
         vec![
@@ -40,13 +91,154 @@ mod tests {
         vec!["tommy".to_string(), "titan".to_string()]
     }
 
+    /// Querier that answers cw721 queries for every contract address.
+    /// Queries that are not cw721 queries are passed to the default [MockQuerier].
+    struct Cw721Querier {
+        base: MockQuerier,
+        /// Mapping of (collection, token_id) to the token's owner
+        owners: BTreeMap<(String, String), String>,
+        /// Addresses that are not cw721 contracts
+        non_cw721: BTreeSet<String>,
+        /// Addresses that are smart contracts, excluding cw721 collections
+        contracts: BTreeSet<String>,
+        /// Addresses denied by the external denylist contract at [DENYLIST_ADDR]
+        denied: BTreeSet<String>,
+        /// Addresses whose contract info can not be queried
+        unreachable: BTreeSet<String>,
+    }
+
+    impl Cw721Querier {
+        fn set_owner(&mut self, collection: &str, token_id: &str, owner: &str) {
+            self.owners
+                .insert((collection.into(), token_id.into()), owner.into());
+        }
+
+        fn handle_cw721(&self, collection: String, msg: Cw721QueryMsg) -> QuerierResult {
+            let result = match msg {
+                Cw721QueryMsg::OwnerOf { token_id, .. } => {
+                    match self.owners.get(&(collection, token_id)) {
+                        Some(owner) => to_binary(&OwnerOfResponse {
+                            owner: owner.to_owned(),
+                            approvals: vec![],
+                        }),
+                        None => Err(StdError::not_found("cw721_base::state::TokenInfo")),
+                    }
+                }
+                Cw721QueryMsg::Tokens {
+                    owner,
+                    start_after,
+                    limit,
+                } => {
+                    let tokens = self
+                        .owners
+                        .iter()
+                        .filter(|((coll, token_id), token_owner)| {
+                            coll == &collection
+                                && token_owner == &&owner
+                                && start_after.as_ref().map_or(true, |start| token_id > start)
+                        })
+                        .take(limit.unwrap_or(10) as usize)
+                        .map(|((_, token_id), _)| token_id.to_owned())
+                        .collect();
+                    to_binary(&TokensResponse { tokens })
+                }
+                Cw721QueryMsg::ContractInfo {} => to_binary(&ContractInfoResponse {
+                    name: collection.to_owned(),
+                    symbol: "NFT".into(),
+                }),
+                Cw721QueryMsg::NumTokens {} => to_binary(&NumTokensResponse {
+                    count: self
+                        .owners
+                        .keys()
+                        .filter(|(coll, _)| coll == &collection)
+                        .count() as u64,
+                }),
+                Cw721QueryMsg::NftInfo { token_id } => to_binary(&NftInfoResponse {
+                    token_uri: Some(format!("ipfs://{}/{}", collection, token_id)),
+                    extension: Empty {},
+                }),
+                _ => Err(StdError::generic_err("Unsupported cw721 query")),
+            };
+            SystemResult::Ok(result.into())
+        }
+    }
+
+    impl Querier for Cw721Querier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let request: QueryRequest<TerraQueryWrapper> = from_slice(bin_request).unwrap();
+            match request {
+                QueryRequest::Custom(TerraQueryWrapper {
+                    query_data: TerraQuery::ContractInfo { contract_address },
+                    ..
+                }) => {
+                    if self.unreachable.contains(&contract_address) {
+                        return SystemResult::Ok(ContractResult::Err("node unavailable".into()));
+                    }
+                    if !self.contracts.contains(&contract_address) {
+                        return SystemResult::Ok(ContractResult::Err(format!(
+                            "{}: no such contract",
+                            contract_address
+                        )));
+                    }
+                    SystemResult::Ok(
+                        to_binary(&TerraContractInfoResponse {
+                            address: contract_address,
+                            creator: CREATOR.into(),
+                            code_id: 1,
+                            admin: None,
+                        })
+                        .into(),
+                    )
+                }
+                QueryRequest::Wasm(WasmQuery::Smart { contract_addr, .. })
+                    if self.non_cw721.contains(&contract_addr) =>
+                {
+                    SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr,
+                    })
+                }
+                QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                    if contract_addr == DENYLIST_ADDR =>
+                {
+                    let DenylistQueryMsg::IsDenied { address } = from_binary(&msg).unwrap();
+                    SystemResult::Ok(
+                        to_binary(&IsDeniedResponse {
+                            denied: self.denied.contains(&address),
+                        })
+                        .into(),
+                    )
+                }
+                QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                    self.handle_cw721(contract_addr, from_binary(&msg).unwrap())
+                }
+                _ => self.base.raw_query(bin_request),
+            }
+        }
+    }
+
+    /// Helper function to create dependencies that can answer cw721 queries
+    fn mock_deps_cw721() -> OwnedDeps<MockStorage, MockApi, Cw721Querier> {
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: Cw721Querier {
+                base: MockQuerier::new(&[]),
+                owners: BTreeMap::new(),
+                non_cw721: BTreeSet::new(),
+                contracts: BTreeSet::new(),
+                denied: BTreeSet::new(),
+                unreachable: BTreeSet::new(),
+            },
+        }
+    }
+
     /// Helper function to instantiate our contract for other tests
     fn do_instantiate(
         deps: DepsMut,
         admins: Vec<String>,
         operators: Vec<String>,
     ) -> Result<Response, ContractError> {
-        let info = mock_info(&CREATOR, &[]);
+        let info = mock_info(CREATOR, &[]);
         let env = mock_env();
         let msg = InstantiateMsg { admins, operators };
         instantiate(deps, env, info, msg)
@@ -131,7 +323,7 @@ mod tests {
          * Verify admins were correctly updated when an admin sender is used
          */
 
-        let info_success = mock_info(&CREATOR, &[]);
+        let info_success = mock_info(CREATOR, &[]);
         let admins_add = vec!["willie".to_string()];
         let admins_rem = vec!["champ".to_string()];
 
@@ -160,7 +352,7 @@ mod tests {
          * Verify admins are not updated when an non-admin sender is used
          */
 
-        let info_fail = mock_info(&"nonadmin", &[]);
+        let info_fail = mock_info("nonadmin", &[]);
         // Try to reset admins back to initial_admins
         // Flipping admins_rem & admins_add from the previous test would reset the the contract's admin state
         // back to the initial value of initial_admins
@@ -192,7 +384,7 @@ mod tests {
          * Verify opers were correctly updated when an admin sender is used
          */
 
-        let info_success = mock_info(&CREATOR, &[]);
+        let info_success = mock_info(CREATOR, &[]);
         let opers_add = vec!["willie".to_string()];
         let opers_rem = vec!["tommy".to_string()];
 
@@ -221,7 +413,7 @@ mod tests {
          * Verify opers are not updated when an non-admin sender (including operators) is used
          */
 
-        let info_fail = mock_info(&"nonadmin", &[]);
+        let info_fail = mock_info("nonadmin", &[]);
         // Try to reset operators back to initial_opers
         // Flipping opers_rem & opers_add from the previous test would reset the the contract's operator state
         // back to the initial value of initial_opers
@@ -245,7 +437,7 @@ mod tests {
     #[test]
     fn update_collection_mappings() {
         // Instantiate contract
-        let mut deps = mock_dependencies(&[]);
+        let mut deps = mock_deps_cw721();
         let initial_admins = get_admins();
         let initial_opers = get_opers();
         do_instantiate(deps.as_mut(), initial_admins.clone(), initial_opers).unwrap();
@@ -259,10 +451,12 @@ mod tests {
             CollectionMapping {
                 source: "terra contract 1".to_string(),
                 destination: "secret contract 1".to_string(),
+                allowlist_only: None,
             },
             CollectionMapping {
                 source: "terra contract 2".to_string(),
                 destination: "secret contract 2".to_string(),
+                allowlist_only: None,
             },
         ];
         let err =
@@ -285,7 +479,10 @@ mod tests {
 
         let sources = vec!["terra contract 1".into(), "terra contract 2".into()];
         let dest_bin = query_collection_mappings(deps.as_ref(), sources).unwrap();
-        let CollectionMappingResponse { destinations } = from_binary(&dest_bin).unwrap();
+        let CollectionMappingResponse {
+            destinations,
+            collection_info,
+        } = from_binary(&dest_bin).unwrap();
 
         let res_success = vec![
             deps.api.addr_validate("secret contract 1").unwrap(),
@@ -293,6 +490,41 @@ mod tests {
         ];
         assert_eq!(destinations, res_success);
 
+        // Contract info is recorded alongside each mapping
+        let coll_info_success = vec![
+            Some(CollectionInfo {
+                name: "terra contract 1".into(),
+                symbol: "NFT".into(),
+            }),
+            Some(CollectionInfo {
+                name: "terra contract 2".into(),
+                symbol: "NFT".into(),
+            }),
+        ];
+        assert_eq!(collection_info, coll_info_success);
+
+        /*
+         * Operator cannot map a source that is not a cw721 collection
+         */
+
+        deps.querier.non_cw721.insert("terra wallet".into());
+        let add_fail = vec![CollectionMapping {
+            source: "terra wallet".to_string(),
+            destination: "secret contract 3".to_string(),
+            allowlist_only: None,
+        }];
+        let err = try_update_collection_mappings(
+            deps.as_mut(),
+            info_success.clone(),
+            None,
+            Some(add_fail),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Address \"terra wallet\" is not a cw721 collection"
+        );
+
         /*
          * Operator can remove items from the collection mappings
          */
@@ -300,6 +532,7 @@ mod tests {
         let rem_list = vec![CollectionMapping {
             source: "terra contract 1".to_string(),
             destination: "secret contract 1".to_string(),
+            allowlist_only: None,
         }];
         try_update_collection_mappings(deps.as_mut(), info_success.clone(), Some(rem_list), None)
             .unwrap();
@@ -307,7 +540,7 @@ mod tests {
         // TODO: Make this check the error when querying terra contract 1
         let sources = vec!["terra contract 2".to_string()];
         let dest_bin = query_collection_mappings(deps.as_ref(), sources).unwrap();
-        let CollectionMappingResponse { destinations } = from_binary(&dest_bin).unwrap();
+        let CollectionMappingResponse { destinations, .. } = from_binary(&dest_bin).unwrap();
         let res_success = vec![deps.api.addr_validate("secret contract 2").unwrap()];
         assert_eq!(destinations, res_success);
 
@@ -323,10 +556,12 @@ mod tests {
         let rem_list = vec![CollectionMapping {
             source: "terra contract 2".to_string(),
             destination: "secret contract 2".to_string(),
+            allowlist_only: None,
         }];
         let add_list = vec![CollectionMapping {
             source: "terra contract 2".to_string(),
             destination: "secret contract 2.0".to_string(),
+            allowlist_only: None,
         }];
         try_update_collection_mappings(
             deps.as_mut(),
@@ -338,7 +573,7 @@ mod tests {
 
         let sources = vec!["terra contract 2".to_string()];
         let dest_bin = query_collection_mappings(deps.as_ref(), sources).unwrap();
-        let CollectionMappingResponse { destinations } = from_binary(&dest_bin).unwrap();
+        let CollectionMappingResponse { destinations, .. } = from_binary(&dest_bin).unwrap();
         assert_eq!(destinations.len(), 1);
 
         let res_success = vec![deps.api.addr_validate("secret contract 2.0").unwrap()];
@@ -348,7 +583,7 @@ mod tests {
     #[test]
     fn receive_nft() {
         // Instantiate contract
-        let mut deps = mock_dependencies(&[]);
+        let mut deps = mock_deps_cw721();
         let info_admin = mock_info(CREATOR, &[]);
         let env = mock_env();
         let initial_admins = get_admins();
@@ -383,6 +618,7 @@ mod tests {
         let add_list = vec![CollectionMapping {
             source: terra_coll_addr.into(),
             destination: "secret contract".into(),
+            allowlist_only: None,
         }];
         try_update_collection_mappings(
             deps.as_mut(),
@@ -410,7 +646,7 @@ mod tests {
             Some(1u8),
         )
         .unwrap();
-        let response: HistoryResponse = from_binary(&response_bin).unwrap();
+        let response: HistoryResponse = from_binary(response_bin).unwrap();
 
         // Verify success
         let res_success = HistoryResponse {
@@ -423,6 +659,11 @@ mod tests {
                 destination_collection: "secret contract".into(),
                 block_height: env.block.height,
                 block_time: env.block.time.seconds(),
+                payload_hash: None,
+                delivery_mode: None,
+                seq: Some(1),
+                status: Some(BridgeStatus::Pending),
+                sn_tx_hash: None,
             }],
         };
         assert_eq!(response, res_success);
@@ -482,7 +723,7 @@ mod tests {
     #[test]
     fn release_nft() {
         // Instantiate contract
-        let mut deps = mock_dependencies(&[]);
+        let mut deps = mock_deps_cw721();
         let info_admin = mock_info(CREATOR, &[]);
         let env = mock_env();
         let initial_admins = get_admins();
@@ -495,6 +736,7 @@ mod tests {
         let add_list = vec![CollectionMapping {
             source: terra_coll_addr.to_owned(),
             destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
         }];
         try_update_collection_mappings(
             deps.as_mut(),
@@ -519,6 +761,32 @@ mod tests {
         )
         .unwrap();
 
+        /*
+         * Operator cannot release an NFT that the bridge does not hold
+         */
+
+        deps.querier
+            .set_owner(&terra_coll_addr, &token_id, "someone else");
+        let err = try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            sn_coll_addr.to_owned(),
+            sn_sender.to_owned(),
+            "terra recipient".into(),
+            token_id.to_owned(),
+            Some(false),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Token \"token_id\" is not held by the bridge"
+        );
+        deps.querier
+            .set_owner(&terra_coll_addr, &token_id, MOCK_CONTRACT_ADDR);
+
         /*
          * Non-operator cannot release an NFT from the bridge
          */
@@ -533,7 +801,9 @@ mod tests {
             sn_sender.to_owned(),
             recipient.to_owned(),
             token_id.to_owned(),
-            false,
+            Some(false),
+            None,
+            None,
         )
         .unwrap_err();
         assert_eq!(err.to_string(), "Unauthorized");
@@ -551,13 +821,20 @@ mod tests {
             sn_sender.to_owned(),
             recipient.to_owned(),
             token_id.to_owned(),
-            false,
+            Some(false),
+            None,
+            None,
         )
         .unwrap();
         assert_eq!(response.messages.len(), 1);
 
-        // Get the history id to test the response
-        let history_id = response.attributes.last().unwrap().value.to_owned();
+        // Get the history id and chain head to test the response
+        let attribute = |key: &str| {
+            let attr = response.attributes.iter().find(|attr| attr.key == key);
+            attr.unwrap().value.to_owned()
+        };
+        let history_id = attribute("history_id");
+        let chain_head = attribute("history_chain_head");
 
         // Construct expected WasmMsg
         let transfer_bin = to_binary(&Cw721ExecuteMsg::TransferNft {
@@ -574,14 +851,16 @@ mod tests {
         assert_eq!(
             response,
             Response::new()
-                .add_message(expected)
+                .add_submessage(SubMsg::reply_always(expected, 1))
                 .add_attribute("action", "transfer_nft")
                 .add_attribute("secret_sender", sn_sender.to_owned())
                 .add_attribute("recipient", recipient.to_owned())
                 .add_attribute("terra_collection", terra_coll_addr)
                 .add_attribute("secret_collection", sn_coll_addr.to_owned())
                 .add_attribute("token_id", token_id.to_owned())
-                .add_attribute("history_id", history_id.to_string())
+                .add_attribute("history_id", history_id)
+                .add_attribute("history_chain_seq", "3")
+                .add_attribute("history_chain_head", chain_head)
         );
 
         /*
@@ -599,7 +878,9 @@ mod tests {
             sn_sender.to_owned(),
             recipient.to_owned(),
             token_id.to_owned(),
-            false,
+            Some(false),
+            None,
+            None,
         )
         .unwrap_err();
         assert_eq!(
@@ -625,7 +906,9 @@ mod tests {
             sn_sender,
             recipient,
             token_id,
-            false,
+            Some(false),
+            None,
+            None,
         )
         .unwrap_err();
         assert_eq!(
@@ -633,4 +916,4052 @@ mod tests {
             "Bridge is in the paused state. Tokens cannot be transfered in or out."
         );
     }
+
+    #[test]
+    fn escrowed_tokens() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        // Generate collection mappings
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+
+        // Send three NFTs to the contract from two different depositors
+        let info_contract = mock_info(&terra_coll_addr, &[]);
+        let sn_sender = "secret sender".to_string();
+        for (sender, token_id) in [("alice", "1"), ("bob", "2"), ("alice", "3")] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                info_contract.to_owned(),
+                sender.into(),
+                token_id.into(),
+                to_binary(&sn_sender).unwrap(),
+            )
+            .unwrap();
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+
+        let expected = |depositor: &str, token_id: &str| EscrowedTokenResponse {
+            collection: terra_coll_addr.to_owned(),
+            token_id: token_id.into(),
+            depositor: depositor.into(),
+            destination_address: sn_sender.to_owned(),
+            block_height: env.block.height,
+        };
+
+        /*
+         * Escrowed tokens are listed per collection and paginated
+         */
+
+        let response: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens(deps.as_ref(), terra_coll_addr.to_owned(), None, Some(2))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            response.tokens,
+            vec![expected("alice", "1"), expected("bob", "2")]
+        );
+
+        let response: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens(
+                deps.as_ref(),
+                terra_coll_addr.to_owned(),
+                Some("2".into()),
+                None,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response.tokens, vec![expected("alice", "3")]);
+
+        /*
+         * Escrowed tokens are listed per depositor
+         */
+
+        let response: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens_by_depositor(deps.as_ref(), "alice".into(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            response.tokens,
+            vec![expected("alice", "1"), expected("alice", "3")]
+        );
+
+        let response: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens_by_depositor(deps.as_ref(), "alice".into(), None, Some(1))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response.tokens, vec![expected("alice", "1")]);
+
+        let start_after = TokenKey {
+            collection: terra_coll_addr.to_owned(),
+            token_id: "1".into(),
+        };
+        let response: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens_by_depositor(
+                deps.as_ref(),
+                "alice".into(),
+                Some(start_after),
+                None,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response.tokens, vec![expected("alice", "3")]);
+
+        /*
+         * Released tokens are removed from escrow
+         */
+
+        try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            sn_coll_addr,
+            sn_sender.to_owned(),
+            "terra recipient".into(),
+            "1".into(),
+            Some(false),
+            None,
+            None,
+        )
+        .unwrap();
+
+        let response: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens(deps.as_ref(), terra_coll_addr.to_owned(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            response.tokens,
+            vec![expected("bob", "2"), expected("alice", "3")]
+        );
+
+        let response: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens_by_depositor(deps.as_ref(), "alice".into(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response.tokens, vec![expected("alice", "3")]);
+
+        /*
+         * Overwriting a token's escrow moves it to the new depositor
+         */
+
+        let escrow = EscrowRecord {
+            depositor: Addr::unchecked("alice"),
+            destination_address: sn_sender.to_owned(),
+            block_height: env.block.height,
+        };
+        save_escrow(
+            deps.as_mut().storage,
+            Addr::unchecked(&terra_coll_addr),
+            "2".into(),
+            escrow,
+        )
+        .unwrap();
+
+        let response: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens_by_depositor(deps.as_ref(), "bob".into(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert!(response.tokens.is_empty());
+
+        let response: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens_by_depositor(deps.as_ref(), "alice".into(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            response.tokens,
+            vec![expected("alice", "2"), expected("alice", "3")]
+        );
+    }
+
+    #[test]
+    fn reconcile_escrow() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: "secret contract".into(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+
+        // Escrow tokens 1 - 4 through the bridge
+        let info_contract = mock_info(&terra_coll_addr, &[]);
+        for token_id in ["1", "2", "3", "4"] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                info_contract.to_owned(),
+                "alice".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+
+        // Token 2 left the bridge and token 5 arrived without ReceiveNft
+        deps.querier.set_owner(&terra_coll_addr, "2", "bob");
+        deps.querier
+            .set_owner(&terra_coll_addr, "5", MOCK_CONTRACT_ADDR);
+
+        /*
+         * Mismatches are reported one page at a time
+         */
+
+        let response: ReconcileEscrowResponse = from_binary(
+            &query_reconcile_escrow(
+                deps.as_ref(),
+                env.to_owned(),
+                terra_coll_addr.to_owned(),
+                None,
+                Some(3),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            ReconcileEscrowResponse {
+                missing: vec![MissingTokenResponse {
+                    token_id: "2".into(),
+                    depositor: "alice".into(),
+                    owner: Some("bob".into()),
+                }],
+                unexpected: vec![],
+                last_token_id: Some("3".into()),
+            }
+        );
+
+        let response: ReconcileEscrowResponse = from_binary(
+            &query_reconcile_escrow(
+                deps.as_ref(),
+                env,
+                terra_coll_addr,
+                response.last_token_id,
+                Some(3),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            ReconcileEscrowResponse {
+                missing: vec![],
+                unexpected: vec!["5".into()],
+                last_token_id: None,
+            }
+        );
+    }
+
+    #[test]
+    fn receive_nft_metadata() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: "secret contract".into(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+
+        /*
+         * Receiving an NFT returns the collection's metadata and stores a snapshot of it
+         */
+
+        let response = try_receive_nft(
+            deps.as_mut(),
+            env,
+            mock_info(&terra_coll_addr, &[]),
+            "terra wallet".into(),
+            "7".into(),
+            to_binary(&"secret wallet").unwrap(),
+        )
+        .unwrap();
+
+        let nft_info = to_binary(&NftInfoResponse {
+            token_uri: Some("ipfs://terra contract/7".to_string()),
+            extension: Empty {},
+        })
+        .unwrap();
+        let hash = Binary::from(Sha256::digest(nft_info.as_slice()).as_slice());
+        assert_eq!(response.data, Some(nft_info.to_owned()));
+
+        let attribute = |key: &str| {
+            response
+                .attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.to_owned())
+        };
+        assert_eq!(
+            attribute("token_uri"),
+            Some("ipfs://terra contract/7".into())
+        );
+        assert_eq!(attribute("metadata_hash"), Some(hash.to_base64()));
+
+        let snapshot: TokenMetadataResponse =
+            from_binary(&query_token_metadata(deps.as_ref(), terra_coll_addr, "7".into()).unwrap())
+                .unwrap();
+        assert_eq!(
+            snapshot,
+            TokenMetadataResponse {
+                nft_info,
+                hash,
+                history_id: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn release_nft_payload() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+
+        let token_id = "token_id".to_string();
+        let sn_sender = "secret sender".to_string();
+        try_receive_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info(&terra_coll_addr, &[]),
+            "terra sender".into(),
+            token_id.to_owned(),
+            to_binary(&sn_sender).unwrap(),
+        )
+        .unwrap();
+        deps.querier
+            .set_owner(&terra_coll_addr, &token_id, MOCK_CONTRACT_ADDR);
+
+        /*
+         * Payloads cannot be forwarded to non-contract recipients
+         */
+
+        let payload = Binary::from(b"{\"stake\":{}}".to_vec());
+        let err = try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            sn_coll_addr.to_owned(),
+            sn_sender.to_owned(),
+            "terra wallet".into(),
+            token_id.to_owned(),
+            Some(false),
+            Some(payload.to_owned()),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "A payload can only be forwarded to a contract recipient"
+        );
+
+        /*
+         * Payloads larger than MAX_PAYLOAD_SIZE are rejected
+         */
+
+        let err = try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            sn_coll_addr.to_owned(),
+            sn_sender.to_owned(),
+            "terra vault".into(),
+            token_id.to_owned(),
+            Some(true),
+            Some(Binary::from(vec![0u8; MAX_PAYLOAD_SIZE + 1])),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Payload of {} bytes exceeds the maximum of {} bytes",
+                MAX_PAYLOAD_SIZE + 1,
+                MAX_PAYLOAD_SIZE
+            )
+        );
+
+        /*
+         * Payloads are forwarded verbatim to contract recipients and hashed in history
+         */
+
+        let response = try_release_nft(
+            deps.as_mut(),
+            env,
+            info_oper,
+            sn_coll_addr,
+            sn_sender,
+            "terra vault".into(),
+            token_id.to_owned(),
+            Some(true),
+            Some(payload.to_owned()),
+            None,
+        )
+        .unwrap();
+
+        let send_bin = to_binary(&Cw721ExecuteMsg::SendNft {
+            contract: "terra vault".into(),
+            token_id: token_id.to_owned(),
+            msg: payload.to_owned(),
+        })
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: terra_coll_addr.to_owned(),
+                msg: send_bin,
+                funds: vec![],
+            }
+            .into()
+        );
+
+        let history: HistoryResponse = from_binary(
+            &query_history(deps.as_ref(), terra_coll_addr, token_id, None, Some(1)).unwrap(),
+        )
+        .unwrap();
+        let hash = Binary::from(Sha256::digest(payload.as_slice()).as_slice());
+        assert_eq!(history.history[0].payload_hash, Some(hash));
+    }
+
+    #[test]
+    fn release_nft_delivery_mode() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+        deps.querier.contracts.insert("terra vault".into());
+
+        // Escrow a token and release it to `recipient`, returning the transfer message
+        let mut release = |token_id: &str, recipient: &str, is_contract: Option<bool>| {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+            let response = try_release_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                info_oper.to_owned(),
+                sn_coll_addr.to_owned(),
+                "secret sender".into(),
+                recipient.into(),
+                token_id.into(),
+                is_contract,
+                None,
+                None,
+            )
+            .unwrap();
+            let history: HistoryResponse = from_binary(
+                &query_history(
+                    deps.as_ref(),
+                    terra_coll_addr.to_owned(),
+                    token_id.into(),
+                    None,
+                    Some(1),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            (
+                response.messages[0].msg.to_owned(),
+                history.history[0].delivery_mode.to_owned(),
+            )
+        };
+        let transfer = |msg: Cw721ExecuteMsg| {
+            WasmMsg::Execute {
+                contract_addr: "terra contract".into(),
+                msg: to_binary(&msg).unwrap(),
+                funds: vec![],
+            }
+            .into()
+        };
+
+        /*
+         * Contract recipients are detected and sent the NFT with SendNft
+         */
+
+        let (msg, mode) = release("1", "terra vault", None);
+        assert_eq!(
+            msg,
+            transfer(Cw721ExecuteMsg::SendNft {
+                contract: "terra vault".into(),
+                token_id: "1".into(),
+                msg: Binary::default(),
+            })
+        );
+        assert_eq!(mode, Some(DeliveryMode::Send));
+
+        /*
+         * Wallet recipients are detected and sent the NFT with TransferNft
+         */
+
+        let (msg, mode) = release("2", "terra wallet", None);
+        assert_eq!(
+            msg,
+            transfer(Cw721ExecuteMsg::TransferNft {
+                recipient: "terra wallet".into(),
+                token_id: "2".into(),
+            })
+        );
+        assert_eq!(mode, Some(DeliveryMode::Transfer));
+
+        /*
+         * Operators can override the detected delivery mode
+         */
+
+        let (msg, mode) = release("3", "terra vault", Some(false));
+        assert_eq!(
+            msg,
+            transfer(Cw721ExecuteMsg::TransferNft {
+                recipient: "terra vault".into(),
+                token_id: "3".into(),
+            })
+        );
+        assert_eq!(mode, Some(DeliveryMode::Transfer));
+
+        /*
+         * Recipients that can not be checked are not assumed to be wallets
+         */
+
+        deps.querier.unreachable.insert("terra flaky".into());
+        try_receive_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info(&terra_coll_addr, &[]),
+            "terra sender".into(),
+            "4".into(),
+            to_binary(&"secret sender").unwrap(),
+        )
+        .unwrap();
+        deps.querier
+            .set_owner(&terra_coll_addr, "4", MOCK_CONTRACT_ADDR);
+        let mut release_flaky = |is_contract: Option<bool>| {
+            try_release_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                info_oper.to_owned(),
+                sn_coll_addr.to_owned(),
+                "secret sender".into(),
+                "terra flaky".into(),
+                "4".into(),
+                is_contract,
+                None,
+                None,
+            )
+        };
+        let err = release_flaky(None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Querier contract error: node unavailable"
+        );
+        release_flaky(Some(true)).unwrap();
+    }
+
+    #[test]
+    fn failed_release() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+
+        let token_id = "token_id".to_string();
+        try_receive_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info(&terra_coll_addr, &[]),
+            "terra sender".into(),
+            token_id.to_owned(),
+            to_binary(&"secret sender").unwrap(),
+        )
+        .unwrap();
+        deps.querier
+            .set_owner(&terra_coll_addr, &token_id, MOCK_CONTRACT_ADDR);
+
+        let response = try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            sn_coll_addr.to_owned(),
+            "secret sender".into(),
+            "terra recipient".into(),
+            token_id.to_owned(),
+            Some(false),
+            None,
+            None,
+        )
+        .unwrap();
+        let sub_msg = response.messages[0].to_owned();
+        let history: HistoryResponse = from_binary(
+            &query_history(
+                deps.as_ref(),
+                terra_coll_addr.to_owned(),
+                token_id.to_owned(),
+                None,
+                Some(1),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(history.history[0].status, Some(BridgeStatus::Completed));
+
+        /*
+         * A failed transfer is recorded and the token is returned to escrow
+         */
+
+        reply(
+            deps.as_mut(),
+            env.to_owned(),
+            Reply {
+                id: sub_msg.id,
+                result: ContractResult::Err("transfer hook rejected".into()),
+            },
+        )
+        .unwrap();
+
+        let failed: FailedReleasesResponse = from_binary(
+            &query_failed_releases(deps.as_ref(), terra_coll_addr.to_owned(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            failed.releases,
+            vec![FailedReleaseResponse {
+                token_id: token_id.to_owned(),
+                recipient: "terra recipient".into(),
+                sn_collection: sn_coll_addr.to_owned(),
+                sn_address: "secret sender".into(),
+                history_id: 2,
+                error: "transfer hook rejected".into(),
+                block_height: env.block.height,
+            }]
+        );
+
+        let escrowed: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens(deps.as_ref(), terra_coll_addr.to_owned(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(escrowed.tokens.len(), 1);
+
+        let history: HistoryResponse = from_binary(
+            &query_history(
+                deps.as_ref(),
+                terra_coll_addr.to_owned(),
+                token_id.to_owned(),
+                None,
+                Some(1),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(history.history[0].status, Some(BridgeStatus::Failed));
+
+        /*
+         * Operators can redirect a failed release to a new recipient
+         */
+
+        let err = try_retry_release(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("not an operator", &[]),
+            terra_coll_addr.to_owned(),
+            token_id.to_owned(),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        let response = try_retry_release(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            terra_coll_addr.to_owned(),
+            token_id.to_owned(),
+            Some("terra wallet".into()),
+            None,
+            None,
+        )
+        .unwrap();
+        let transfer_bin = to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: "terra wallet".into(),
+            token_id: token_id.to_owned(),
+        })
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: terra_coll_addr.to_owned(),
+                msg: transfer_bin,
+                funds: vec![],
+            }
+            .into()
+        );
+
+        // The failed release is cleared once it is retried, and its record is reused
+        let failed: FailedReleasesResponse = from_binary(
+            &query_failed_releases(deps.as_ref(), terra_coll_addr.to_owned(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert!(failed.releases.is_empty());
+        assert_eq!(response.attributes[6], attr("history_id", "2"));
+        let history: HistoryResponse = from_binary(
+            &query_history(
+                deps.as_ref(),
+                terra_coll_addr.to_owned(),
+                token_id.to_owned(),
+                None,
+                None,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(history.history.len(), 2);
+        assert_eq!(
+            history.history[0].source_address,
+            Some("terra wallet".into())
+        );
+        assert_eq!(history.history[0].status, Some(BridgeStatus::Completed));
+
+        // Pending releases are removed by their own reply
+        let reply_id = response.messages[0].id;
+        assert_eq!(reply_id, sub_msg.id + 1);
+        let succeeded = Reply {
+            id: reply_id,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: None,
+            }),
+        };
+        reply(deps.as_mut(), env.to_owned(), succeeded.to_owned()).unwrap();
+        assert_eq!(
+            PENDING_RELEASES
+                .may_load(&deps.storage, reply_id.into())
+                .unwrap(),
+            None
+        );
+        let err = reply(deps.as_mut(), env.to_owned(), succeeded).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "terra_bridge::state::PendingRelease not found"
+        );
+
+        let err = try_retry_release(
+            deps.as_mut(),
+            env,
+            info_oper,
+            terra_coll_addr,
+            token_id,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No failed release found for token \"token_id\""
+        );
+    }
+
+    #[test]
+    fn claimable_release() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let info_claimant = mock_info("terra claimant", &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        for token_id in ["1", "2"] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+
+        /*
+         * Operators mark tokens claimable by a recipient
+         */
+
+        let err = try_mark_claimable(
+            deps.as_mut(),
+            env.to_owned(),
+            info_claimant.to_owned(),
+            sn_coll_addr.to_owned(),
+            "secret sender".into(),
+            "terra claimant".into(),
+            "1".into(),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        try_mark_claimable(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            sn_coll_addr.to_owned(),
+            "secret sender".into(),
+            "terra claimant".into(),
+            "1".into(),
+            None,
+        )
+        .unwrap();
+        try_mark_claimable(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            sn_coll_addr.to_owned(),
+            "secret sender".into(),
+            "terra claimant".into(),
+            "2".into(),
+            Some(100),
+        )
+        .unwrap();
+
+        let claims: ClaimsResponse =
+            from_binary(&query_claims(deps.as_ref(), "terra claimant".into(), None, None).unwrap())
+                .unwrap();
+        assert_eq!(
+            claims.claims,
+            vec![
+                ClaimResponse {
+                    collection: terra_coll_addr.to_owned(),
+                    token_id: "1".into(),
+                    sn_collection: sn_coll_addr.to_owned(),
+                    sn_address: "secret sender".into(),
+                    expires_at: None,
+                },
+                ClaimResponse {
+                    collection: terra_coll_addr.to_owned(),
+                    token_id: "2".into(),
+                    sn_collection: sn_coll_addr.to_owned(),
+                    sn_address: "secret sender".into(),
+                    expires_at: Some(env.block.time.seconds() + 100),
+                },
+            ]
+        );
+
+        let claims: ClaimsResponse = from_binary(
+            &query_claims(deps.as_ref(), "terra claimant".into(), None, Some(1)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(claims.claims.len(), 1);
+        assert_eq!(claims.claims[0].token_id, "1");
+
+        let start_after = TokenKey {
+            collection: terra_coll_addr.to_owned(),
+            token_id: "1".into(),
+        };
+        let claims: ClaimsResponse = from_binary(
+            &query_claims(
+                deps.as_ref(),
+                "terra claimant".into(),
+                Some(start_after),
+                None,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(claims.claims.len(), 1);
+        assert_eq!(claims.claims[0].token_id, "2");
+
+        // Marking a token claimable closes its deposit, which can no longer be reclaimed
+        let pending: PendingBridgesResponse = from_binary(
+            &query_pending_bridges(deps.as_ref(), env.to_owned(), None, None, None).unwrap(),
+        )
+        .unwrap();
+        assert!(pending.bridges.is_empty());
+        let err = try_reclaim_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("terra sender", &[]),
+            1,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge record 1 is not awaiting acknowledgement"
+        );
+
+        // Claimable tokens can not be pushed by ReleaseNft
+        let err = try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            sn_coll_addr.to_owned(),
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Token \"1\" is awaiting a claim");
+
+        /*
+         * Only the recipient can claim the token
+         */
+
+        let err = try_claim_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("someone else", &[]),
+            terra_coll_addr.to_owned(),
+            "1".into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        let response = try_claim_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_claimant.to_owned(),
+            terra_coll_addr.to_owned(),
+            "1".into(),
+        )
+        .unwrap();
+        let transfer_bin = to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: "terra claimant".into(),
+            token_id: "1".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: terra_coll_addr.to_owned(),
+                msg: transfer_bin,
+                funds: vec![],
+            }
+            .into()
+        );
+        let history: HistoryResponse = from_binary(
+            &query_history(
+                deps.as_ref(),
+                terra_coll_addr.to_owned(),
+                "1".into(),
+                None,
+                Some(1),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(history.history[0].delivery_mode, Some(DeliveryMode::Claim));
+
+        /*
+         * Expired claims can not be claimed, but admins can revoke them
+         */
+
+        env.block.time = env.block.time.plus_seconds(100);
+        let err = try_claim_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_claimant.to_owned(),
+            terra_coll_addr.to_owned(),
+            "2".into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "The claim for token \"2\" has expired");
+
+        let err = try_revoke_claim(
+            deps.as_mut(),
+            info_claimant.to_owned(),
+            terra_coll_addr.to_owned(),
+            "2".into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        try_revoke_claim(
+            deps.as_mut(),
+            info_admin,
+            terra_coll_addr.to_owned(),
+            "2".into(),
+        )
+        .unwrap();
+        let claims: ClaimsResponse =
+            from_binary(&query_claims(deps.as_ref(), "terra claimant".into(), None, None).unwrap())
+                .unwrap();
+        assert!(claims.claims.is_empty());
+
+        let err = try_claim_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_claimant,
+            terra_coll_addr.to_owned(),
+            "2".into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "No claim found for token \"2\"");
+
+        /*
+         * Expired claims do not block releases, and are removed by them
+         */
+
+        try_mark_claimable(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            sn_coll_addr.to_owned(),
+            "secret sender".into(),
+            "terra claimant".into(),
+            "2".into(),
+            Some(10),
+        )
+        .unwrap();
+        let release = |deps: DepsMut, env: Env| {
+            try_release_nft(
+                deps,
+                env,
+                info_oper.to_owned(),
+                sn_coll_addr.to_owned(),
+                "secret sender".into(),
+                "terra recipient".into(),
+                "2".into(),
+                Some(false),
+                None,
+                None,
+            )
+        };
+        let err = release(deps.as_mut(), env.to_owned()).unwrap_err();
+        assert_eq!(err.to_string(), "Token \"2\" is awaiting a claim");
+
+        env.block.time = env.block.time.plus_seconds(10);
+        release(deps.as_mut(), env).unwrap();
+        let claims: ClaimsResponse =
+            from_binary(&query_claims(deps.as_ref(), "terra claimant".into(), None, None).unwrap())
+                .unwrap();
+        assert!(claims.claims.is_empty());
+    }
+
+    #[test]
+    fn release_nfts() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+
+        for token_id in ["1", "2"] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+
+        let release = |token_id: &str| ReleaseRequest {
+            recipient: "terra recipient".into(),
+            sn_collection: sn_coll_addr.to_owned(),
+            sn_address: "secret sender".into(),
+            token_id: token_id.into(),
+            recipient_is_contract: Some(false),
+            msg: None,
+        };
+        // Token "3" was never bridged
+        let releases = vec![release("1"), release("3"), release("2"), release("1")];
+
+        /*
+         * Only operators can release NFTs
+         */
+
+        let err = try_release_nfts(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("not an operator", &[]),
+            releases.to_owned(),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        /*
+         * By default the whole batch fails when one release is invalid
+         */
+
+        let err = try_release_nfts(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            releases.to_owned(),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Token \"3\" is not held by the bridge");
+
+        /*
+         * Invalid releases can be skipped and reported instead
+         */
+
+        let response = try_release_nfts(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper,
+            releases,
+            Some(true),
+            None,
+        )
+        .unwrap();
+        let transfer = |token_id: &str| WasmMsg::Execute {
+            contract_addr: terra_coll_addr.to_owned(),
+            msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: "terra recipient".into(),
+                token_id: token_id.into(),
+            })
+            .unwrap(),
+            funds: vec![],
+        };
+        // Reply ids keep counting from the rejected batch, which mock storage does not revert
+        assert_eq!(
+            response.messages,
+            vec![
+                SubMsg::reply_always(transfer("1"), 2),
+                SubMsg::reply_always(transfer("2"), 3),
+            ]
+        );
+        let data: ReleaseNftsResponse = from_binary(&response.data.unwrap()).unwrap();
+        assert_eq!(
+            data.skipped,
+            vec![
+                SkippedRelease {
+                    index: 1,
+                    token_id: "3".into(),
+                    error: "Token \"3\" is not held by the bridge".into(),
+                },
+                SkippedRelease {
+                    index: 3,
+                    token_id: "1".into(),
+                    error: "Token \"1\" appears more than once in the batch".into(),
+                },
+            ]
+        );
+
+        let escrowed: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens(deps.as_ref(), terra_coll_addr.to_owned(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert!(escrowed.tokens.is_empty());
+
+        /*
+         * A failed transfer in the batch only returns its own token to escrow
+         */
+
+        reply(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: 3,
+                result: ContractResult::Err("transfer hook rejected".into()),
+            },
+        )
+        .unwrap();
+        let escrowed: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens(deps.as_ref(), terra_coll_addr, None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(escrowed.tokens.len(), 1);
+        assert_eq!(escrowed.tokens[0].token_id, "2");
+    }
+
+    #[test]
+    fn acknowledge_bridge() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: "secret contract".into(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+
+        // Receive token "1", then token "2" an hour later
+        for token_id in ["1", "2"] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+            env.block.time = env.block.time.plus_seconds(3600);
+        }
+        let pending = |deps: &OwnedDeps<MockStorage, MockApi, Cw721Querier>,
+                       older_than: Option<u64>| {
+            let res: PendingBridgesResponse = from_binary(
+                &query_pending_bridges(deps.as_ref(), env.to_owned(), older_than, None, None)
+                    .unwrap(),
+            )
+            .unwrap();
+            res.bridges
+                .into_iter()
+                .map(|bridge| (bridge.seq, bridge.record.token_id))
+                .collect::<Vec<(u64, String)>>()
+        };
+
+        /*
+         * Received tokens are pending until acknowledged
+         */
+
+        assert_eq!(
+            pending(&deps, None),
+            vec![(1, "1".to_string()), (2, "2".to_string())]
+        );
+        assert_eq!(pending(&deps, Some(7200)), vec![(1, "1".to_string())]);
+
+        let err = try_acknowledge_bridge(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("not an operator", &[]),
+            1,
+            None,
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        try_acknowledge_bridge(
+            deps.as_mut(),
+            mock_env(),
+            info_oper.to_owned(),
+            1,
+            Some("sn tx".into()),
+            true,
+        )
+        .unwrap();
+        try_acknowledge_bridge(
+            deps.as_mut(),
+            mock_env(),
+            info_oper.to_owned(),
+            2,
+            None,
+            false,
+        )
+        .unwrap();
+        assert!(pending(&deps, None).is_empty());
+
+        let history = |token_id: &str| {
+            let res: HistoryResponse = from_binary(
+                &query_history(
+                    deps.as_ref(),
+                    terra_coll_addr.to_owned(),
+                    token_id.into(),
+                    None,
+                    None,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            res.history[0].to_owned()
+        };
+        let record = history("1");
+        assert_eq!(record.status, Some(BridgeStatus::Completed));
+        assert_eq!(record.sn_tx_hash, Some("sn tx".into()));
+        let record = history("2");
+        assert_eq!(record.status, Some(BridgeStatus::Failed));
+        assert_eq!(record.sn_tx_hash, None);
+
+        // Records can only be acknowledged once
+        let err = try_acknowledge_bridge(deps.as_mut(), mock_env(), info_oper, 1, None, true)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge record 1 is not awaiting acknowledgement"
+        );
+    }
+
+    #[test]
+    fn reclaim_nft() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let info_depositor = mock_info("terra sender", &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: "secret contract".into(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        try_receive_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info(&terra_coll_addr, &[]),
+            "terra sender".into(),
+            "1".into(),
+            to_binary(&"secret sender").unwrap(),
+        )
+        .unwrap();
+        deps.querier
+            .set_owner(&terra_coll_addr, "1", MOCK_CONTRACT_ADDR);
+        let received_at = env.block.time.seconds();
+
+        /*
+         * Admins configure the reclaim timeout
+         */
+
+        let err =
+            try_update_reclaim_timeout(deps.as_mut(), info_oper.to_owned(), 3600).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_update_reclaim_timeout(deps.as_mut(), info_admin, 3600).unwrap();
+
+        let err = try_reclaim_nft(deps.as_mut(), env.to_owned(), info_depositor.to_owned(), 1)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Bridge record 1 can not be reclaimed until {}",
+                received_at + 3600
+            )
+        );
+
+        /*
+         * Operators can block or delay reclaiming
+         */
+
+        env.block.time = env.block.time.plus_seconds(3600);
+        try_hold_reclaim(deps.as_mut(), info_oper.to_owned(), 1, None).unwrap();
+        let err = try_reclaim_nft(deps.as_mut(), env.to_owned(), info_depositor.to_owned(), 1)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Reclaiming bridge record 1 is blocked by an operator"
+        );
+
+        try_hold_reclaim(
+            deps.as_mut(),
+            info_oper,
+            1,
+            Some(env.block.time.seconds() + 60),
+        )
+        .unwrap();
+        let pending: PendingBridgesResponse = from_binary(
+            &query_pending_bridges(deps.as_ref(), env.to_owned(), None, None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            pending.bridges[0].reclaimable_at,
+            Some(env.block.time.seconds() + 60)
+        );
+
+        /*
+         * Only the depositor can reclaim the token once the timeout passes
+         */
+
+        env.block.time = env.block.time.plus_seconds(60);
+        let err = try_reclaim_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("someone else", &[]),
+            1,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        let response =
+            try_reclaim_nft(deps.as_mut(), env.to_owned(), info_depositor.to_owned(), 1).unwrap();
+        let transfer_bin = to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: "terra sender".into(),
+            token_id: "1".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: terra_coll_addr.to_owned(),
+                msg: transfer_bin,
+                funds: vec![],
+            }
+            .into()
+        );
+
+        // Both the inbound record and the refund are recorded as refunded
+        let history: HistoryResponse = from_binary(
+            &query_history(
+                deps.as_ref(),
+                terra_coll_addr.to_owned(),
+                "1".into(),
+                None,
+                None,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(history.history.len(), 2);
+        assert!(!history.history[0].is_enter);
+        assert!(history
+            .history
+            .iter()
+            .all(|record| record.status == Some(BridgeStatus::Refunded)));
+
+        let escrowed: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens(deps.as_ref(), terra_coll_addr, None, None).unwrap(),
+        )
+        .unwrap();
+        assert!(escrowed.tokens.is_empty());
+
+        let err = try_reclaim_nft(deps.as_mut(), env, info_depositor, 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge record 1 is not awaiting acknowledgement"
+        );
+    }
+
+    #[test]
+    fn wind_down() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: "secret contract".into(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        let receive = |deps: DepsMut, sender: &str, token_id: &str| {
+            try_receive_nft(
+                deps,
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                sender.into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+        };
+        receive(deps.as_mut(), "terra alice", "1").unwrap();
+        receive(deps.as_mut(), "terra bob", "2").unwrap();
+        for token_id in ["1", "2"] {
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+
+        let err = try_withdraw_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("terra alice", &[]),
+            terra_coll_addr.to_owned(),
+            "1".into(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Tokens can only be withdrawn once the bridge has been wound down"
+        );
+
+        /*
+         * Only admins can wind down the bridge, and only once
+         */
+
+        let err =
+            try_wind_down(deps.as_mut(), env.to_owned(), mock_info("tommy", &[])).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        try_wind_down(deps.as_mut(), env.to_owned(), info_admin.to_owned()).unwrap();
+        let err = try_wind_down(deps.as_mut(), env.to_owned(), info_admin.to_owned()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The bridge has been wound down and no longer receives tokens"
+        );
+
+        // Receiving is disabled permanently
+        let err = receive(deps.as_mut(), "terra alice", "3").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The bridge has been wound down and no longer receives tokens"
+        );
+
+        let status: WindDownStatusResponse =
+            from_binary(&query_wind_down_status(deps.as_ref()).unwrap()).unwrap();
+        assert_eq!(
+            status,
+            WindDownStatusResponse {
+                wound_down_at: Some(env.block.time.seconds()),
+                total_remaining: 2,
+                collections: vec![RemainingTokensResponse {
+                    collection: terra_coll_addr.to_owned(),
+                    remaining: 2,
+                }],
+            }
+        );
+
+        /*
+         * Depositors withdraw their own tokens, even while the bridge is paused
+         */
+
+        try_update_pause(deps.as_mut(), info_admin, true, None).unwrap();
+        let err = try_withdraw_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("terra bob", &[]),
+            terra_coll_addr.to_owned(),
+            "1".into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        let response = try_withdraw_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("terra alice", &[]),
+            terra_coll_addr.to_owned(),
+            "1".into(),
+        )
+        .unwrap();
+        let transfer_bin = to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: "terra alice".into(),
+            token_id: "1".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: terra_coll_addr.to_owned(),
+                msg: transfer_bin,
+                funds: vec![],
+            }
+            .into()
+        );
+
+        // The unacknowledged inbound record is refunded
+        let pending: PendingBridgesResponse = from_binary(
+            &query_pending_bridges(deps.as_ref(), env.to_owned(), None, None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(pending.bridges.len(), 1);
+        assert_eq!(pending.bridges[0].record.token_id, "2");
+
+        let status: WindDownStatusResponse =
+            from_binary(&query_wind_down_status(deps.as_ref()).unwrap()).unwrap();
+        assert_eq!(status.total_remaining, 1);
+
+        let err = try_withdraw_nft(
+            deps.as_mut(),
+            env,
+            mock_info("terra alice", &[]),
+            terra_coll_addr,
+            "1".into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Token \"1\" is not held by the bridge");
+    }
+
+    #[test]
+    fn rescue_nft() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: "secret contract".into(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        // Token "1" was bridged, token "2" was transferred directly to the bridge
+        try_receive_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info(&terra_coll_addr, &[]),
+            "terra sender".into(),
+            "1".into(),
+            to_binary(&"secret sender").unwrap(),
+        )
+        .unwrap();
+        for token_id in ["1", "2"] {
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+        deps.querier
+            .set_owner(&terra_coll_addr, "3", "terra wallet");
+
+        // Token "4" was also transferred directly, but is frozen pending a dispute
+        deps.querier
+            .set_owner(&terra_coll_addr, "4", MOCK_CONTRACT_ADDR);
+        try_freeze_token(
+            deps.as_mut(),
+            env.to_owned(),
+            info_admin.to_owned(),
+            terra_coll_addr.to_owned(),
+            "4".into(),
+            "reported stolen".into(),
+        )
+        .unwrap();
+
+        let mut rescue = |info: MessageInfo, token_id: &str| {
+            try_rescue_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                info,
+                terra_coll_addr.to_owned(),
+                token_id.into(),
+                "terra wallet".into(),
+            )
+        };
+
+        /*
+         * Only admins can rescue tokens the bridge owns outside the escrow ledger
+         */
+
+        let err = rescue(mock_info("tommy", &[]), "2").unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        let err = rescue(info_admin.to_owned(), "1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Token \"1\" is in the escrow ledger and can not be rescued"
+        );
+
+        let err = rescue(info_admin.to_owned(), "3").unwrap_err();
+        assert_eq!(err.to_string(), "Token \"3\" is not held by the bridge");
+
+        let err = rescue(info_admin.to_owned(), "4").unwrap_err();
+        assert_eq!(err.to_string(), "Token \"4\" is frozen");
+
+        let response = rescue(info_admin, "2").unwrap();
+        let transfer_bin = to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: "terra wallet".into(),
+            token_id: "2".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: terra_coll_addr.to_owned(),
+                msg: transfer_bin,
+                funds: vec![],
+            }
+            .into()
+        );
+
+        /*
+         * Rescues are recorded in the audit history
+         */
+
+        let rescues: RescuesResponse =
+            from_binary(&query_rescues(deps.as_ref(), None, None).unwrap()).unwrap();
+        assert_eq!(
+            rescues.rescues,
+            vec![RescueResponse {
+                rescue_id: 1,
+                collection: terra_coll_addr,
+                token_id: "2".into(),
+                recipient: "terra wallet".into(),
+                admin: CREATOR.into(),
+                block_height: env.block.height,
+                block_time: env.block.time.seconds(),
+            }]
+        );
+    }
+
+    #[test]
+    fn withdraw_funds() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+        deps.querier
+            .base
+            .update_balance(MOCK_CONTRACT_ADDR, coins(1000, "uluna"));
+
+        let balances: BalancesResponse =
+            from_binary(&query_balances(deps.as_ref(), env.to_owned()).unwrap()).unwrap();
+        assert_eq!(balances.balances, coins(1000, "uluna"));
+
+        /*
+         * Only admins can withdraw native coins
+         */
+
+        let err = try_withdraw_native(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            "terra wallet".into(),
+            coins(400, "uluna"),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        let err = try_withdraw_native(
+            deps.as_mut(),
+            env.to_owned(),
+            info_admin.to_owned(),
+            "terra wallet".into(),
+            coins(0, "uluna"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Withdrawal amount must be greater than zero"
+        );
+
+        let response = try_withdraw_native(
+            deps.as_mut(),
+            env.to_owned(),
+            info_admin.to_owned(),
+            "terra wallet".into(),
+            coins(400, "uluna"),
+        )
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            BankMsg::Send {
+                to_address: "terra wallet".into(),
+                amount: coins(400, "uluna"),
+            }
+            .into()
+        );
+
+        /*
+         * Only admins can withdraw cw20 tokens
+         */
+
+        let err = try_withdraw_cw20(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            "terra token".into(),
+            "terra wallet".into(),
+            Uint128::new(50),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        let response = try_withdraw_cw20(
+            deps.as_mut(),
+            env.to_owned(),
+            info_admin,
+            "terra token".into(),
+            "terra wallet".into(),
+            Uint128::new(50),
+        )
+        .unwrap();
+        let transfer_bin = to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: "terra wallet".into(),
+            amount: Uint128::new(50),
+        })
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: "terra token".into(),
+                msg: transfer_bin,
+                funds: vec![],
+            }
+            .into()
+        );
+
+        /*
+         * Each withdrawal is recorded in the audit history
+         */
+
+        let withdrawals: WithdrawalsResponse =
+            from_binary(&query_withdrawals(deps.as_ref(), None, None).unwrap()).unwrap();
+        let withdrawal = |withdrawal_id: u64, funds: WithdrawnFunds| WithdrawalResponse {
+            withdrawal_id,
+            funds,
+            recipient: "terra wallet".into(),
+            admin: CREATOR.into(),
+            block_height: env.block.height,
+            block_time: env.block.time.seconds(),
+        };
+        assert_eq!(
+            withdrawals.withdrawals,
+            vec![
+                withdrawal(
+                    2,
+                    WithdrawnFunds::Cw20 {
+                        token: Addr::unchecked("terra token"),
+                        amount: Uint128::new(50),
+                    }
+                ),
+                withdrawal(1, WithdrawnFunds::Native(coins(400, "uluna"))),
+            ]
+        );
+    }
+
+    #[test]
+    fn freeze_token() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        let receive = |deps: DepsMut, token_id: &str| {
+            try_receive_nft(
+                deps,
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+        };
+        receive(deps.as_mut(), "1").unwrap();
+        deps.querier
+            .set_owner(&terra_coll_addr, "1", MOCK_CONTRACT_ADDR);
+
+        // Token "1" has a time-locked release waiting in the queue
+        try_update_release_delay(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            terra_coll_addr.to_owned(),
+            Some(600),
+        )
+        .unwrap();
+        try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            sn_coll_addr.to_owned(),
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            Some(false),
+            None,
+            None,
+        )
+        .unwrap();
+
+        /*
+         * Only admins can freeze tokens
+         */
+
+        let mut freeze = |info: MessageInfo, token_id: &str| {
+            try_freeze_token(
+                deps.as_mut(),
+                env.to_owned(),
+                info,
+                terra_coll_addr.to_owned(),
+                token_id.into(),
+                "reported stolen".into(),
+            )
+        };
+        let err = freeze(info_oper.to_owned(), "1").unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        freeze(info_admin.to_owned(), "1").unwrap();
+        freeze(info_admin.to_owned(), "2").unwrap();
+
+        let frozen: FrozenTokensResponse = from_binary(
+            &query_frozen_tokens(deps.as_ref(), terra_coll_addr.to_owned(), None, Some(1)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            frozen.tokens,
+            vec![FrozenTokenResponse {
+                token_id: "1".into(),
+                note: "reported stolen".into(),
+                admin: CREATOR.into(),
+                frozen_at: env.block.time.seconds(),
+            }]
+        );
+
+        /*
+         * Frozen tokens can not be released or received
+         */
+
+        let err = try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper,
+            sn_coll_addr,
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Token \"1\" is frozen");
+
+        let err = receive(deps.as_mut(), "2").unwrap_err();
+        assert_eq!(err.to_string(), "Token \"2\" is frozen");
+
+        /*
+         * Disputes are resolved by unfreezing or redirecting the token
+         */
+
+        try_resolve_dispute(
+            deps.as_mut(),
+            env.to_owned(),
+            info_admin.to_owned(),
+            terra_coll_addr.to_owned(),
+            "2".into(),
+            None,
+        )
+        .unwrap();
+        receive(deps.as_mut(), "2").unwrap();
+
+        let response = try_resolve_dispute(
+            deps.as_mut(),
+            env.to_owned(),
+            info_admin.to_owned(),
+            terra_coll_addr.to_owned(),
+            "1".into(),
+            Some("terra owner".into()),
+        )
+        .unwrap();
+        let transfer_bin = to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: "terra owner".into(),
+            token_id: "1".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: terra_coll_addr.to_owned(),
+                msg: transfer_bin,
+                funds: vec![],
+            }
+            .into()
+        );
+
+        // The redirected token left escrow and its inbound record was closed
+        let escrowed: EscrowedTokensResponse = from_binary(
+            &query_escrowed_tokens(deps.as_ref(), terra_coll_addr.to_owned(), None, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(escrowed.tokens.len(), 1);
+        assert_eq!(escrowed.tokens[0].token_id, "2");
+        let history: HistoryResponse = from_binary(
+            &query_history(
+                deps.as_ref(),
+                terra_coll_addr.to_owned(),
+                "1".into(),
+                None,
+                None,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(history.history[0].status, Some(BridgeStatus::Completed));
+        assert_eq!(history.history[1].status, Some(BridgeStatus::Failed));
+
+        // Its queued release was cancelled
+        assert!(response
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "cancelled_queue_id" && attr.value == "1"));
+        let queued: QueuedReleasesResponse =
+            from_binary(&query_queued_releases(deps.as_ref(), None, None).unwrap()).unwrap();
+        assert!(queued.releases.is_empty());
+
+        let err = try_resolve_dispute(
+            deps.as_mut(),
+            env,
+            info_admin,
+            terra_coll_addr,
+            "1".into(),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Token \"1\" is not frozen");
+    }
+
+    #[test]
+    fn denylist() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        let receive = |deps: DepsMut, sender: &str, sn_sender: &str, token_id: &str| {
+            try_receive_nft(
+                deps,
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                sender.into(),
+                token_id.into(),
+                to_binary(&sn_sender).unwrap(),
+            )
+        };
+        receive(deps.as_mut(), "terra sender", "secret sender", "1").unwrap();
+        deps.querier
+            .set_owner(&terra_coll_addr, "1", MOCK_CONTRACT_ADDR);
+        let release = |deps: DepsMut, recipient: &str, sn_sender: &str| {
+            try_release_nft(
+                deps,
+                env.to_owned(),
+                info_oper.to_owned(),
+                sn_coll_addr.to_owned(),
+                sn_sender.into(),
+                recipient.into(),
+                "1".into(),
+                Some(false),
+                None,
+                None,
+            )
+        };
+
+        /*
+         * Only admins can update the denylist
+         */
+
+        let err = try_update_denylist(
+            deps.as_mut(),
+            info_oper.to_owned(),
+            Some(vec!["terra thief".into()]),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        // Terra addresses are validated, and all entries are stored lowercase
+        let err = try_update_denylist(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            Some(vec!["t".into()]),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid input"));
+
+        try_update_denylist(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            Some(vec!["Terra Thief".into(), "SECRET thief".into()]),
+            None,
+            Some(DENYLIST_ADDR.into()),
+        )
+        .unwrap();
+        let denylist: DenylistResponse =
+            from_binary(&query_denylist(deps.as_ref(), None, None).unwrap()).unwrap();
+        assert_eq!(
+            denylist,
+            DenylistResponse {
+                addresses: vec!["secret thief".into(), "terra thief".into()],
+                contract: Some(Addr::unchecked(DENYLIST_ADDR)),
+            }
+        );
+        let denylist: DenylistResponse =
+            from_binary(&query_denylist(deps.as_ref(), Some("SECRET Thief".into()), None).unwrap())
+                .unwrap();
+        assert_eq!(denylist.addresses, vec!["terra thief".to_string()]);
+
+        /*
+         * Denied Terra and SN addresses can not receive or release
+         */
+
+        let err = receive(deps.as_mut(), "terra thief", "secret sender", "2").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Address \"terra thief\" is not allowed to use the bridge"
+        );
+        let err = receive(deps.as_mut(), "terra sender", "secret thief", "2").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Address \"secret thief\" is not allowed to use the bridge"
+        );
+        let err = release(deps.as_mut(), "terra thief", "secret sender").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Address \"terra thief\" is not allowed to use the bridge"
+        );
+        let err = release(deps.as_mut(), "terra recipient", "secret thief").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Address \"secret thief\" is not allowed to use the bridge"
+        );
+
+        // Addresses denied by the external contract are rejected too
+        deps.querier.denied.insert("terra sanctioned".into());
+        let err = release(deps.as_mut(), "terra sanctioned", "secret sender").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Address \"terra sanctioned\" is not allowed to use the bridge"
+        );
+
+        /*
+         * Removed addresses can use the bridge again
+         */
+
+        try_update_denylist(
+            deps.as_mut(),
+            info_admin,
+            None,
+            Some(vec!["Terra THIEF".into()]),
+            Some("".into()),
+        )
+        .unwrap();
+        release(deps.as_mut(), "terra thief", "secret sender").unwrap();
+    }
+
+    #[test]
+    fn allowlist() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: "secret contract".into(),
+            allowlist_only: Some(true),
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        let receive = |deps: DepsMut, sender: &str, token_id: &str| {
+            try_receive_nft(
+                deps,
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                sender.into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+        };
+        let allowlist = |deps: &OwnedDeps<MockStorage, MockApi, Cw721Querier>, address: &str| {
+            let res: AllowlistResponse = from_binary(
+                &query_allowlist(deps.as_ref(), terra_coll_addr.to_owned(), address.into())
+                    .unwrap(),
+            )
+            .unwrap();
+            res
+        };
+
+        /*
+         * Only allowlisted addresses can bridge in allowlist-only mode
+         */
+
+        let err = receive(deps.as_mut(), "terra beta", "1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Address \"terra beta\" is not allowlisted for this collection"
+        );
+
+        let err = try_update_allowlist(
+            deps.as_mut(),
+            mock_info("not an operator", &[]),
+            terra_coll_addr.to_owned(),
+            Some(vec!["terra beta".into()]),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        try_update_allowlist(
+            deps.as_mut(),
+            info_oper.to_owned(),
+            terra_coll_addr.to_owned(),
+            Some(vec!["terra beta".into(), "terra tester".into()]),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            allowlist(&deps, "terra beta"),
+            AllowlistResponse {
+                allowlist_only: true,
+                is_allowlisted: true,
+            }
+        );
+        receive(deps.as_mut(), "terra beta", "1").unwrap();
+
+        /*
+         * Removed addresses can no longer bridge
+         */
+
+        try_update_allowlist(
+            deps.as_mut(),
+            info_oper.to_owned(),
+            terra_coll_addr.to_owned(),
+            None,
+            Some(vec!["terra beta".into()]),
+            None,
+        )
+        .unwrap();
+        assert!(!allowlist(&deps, "terra beta").is_allowlisted);
+        assert!(allowlist(&deps, "terra tester").is_allowlisted);
+        let err = receive(deps.as_mut(), "terra beta", "2").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Address \"terra beta\" is not allowlisted for this collection"
+        );
+
+        /*
+         * Only admins can turn allowlist-only mode off for a mapped collection
+         */
+
+        let set_mode = |deps: DepsMut, info: MessageInfo, collection: &str| {
+            try_update_allowlist(deps, info, collection.into(), None, None, Some(false))
+        };
+        let err = set_mode(deps.as_mut(), info_oper, &terra_coll_addr).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        let err = set_mode(deps.as_mut(), info_admin.to_owned(), "terra unmapped").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Collection mapping not found for Terra address \"terra unmapped\""
+        );
+
+        set_mode(deps.as_mut(), info_admin, &terra_coll_addr).unwrap();
+        assert!(!allowlist(&deps, "terra beta").allowlist_only);
+        receive(deps.as_mut(), "terra beta", "2").unwrap();
+    }
+
+    #[test]
+    fn rate_limits() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        /*
+         * Only admins can set rate limits
+         */
+
+        let limits = RateLimits {
+            window: 3600,
+            max_releases_per_collection: Some(2),
+            max_receives_per_user: Some(2),
+            max_releases_per_operator: Some(2),
+        };
+        let err = try_update_rate_limits(
+            deps.as_mut(),
+            mock_info("tommy", &[]),
+            Some(limits.to_owned()),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_update_rate_limits(deps.as_mut(), info_admin, Some(limits.to_owned())).unwrap();
+
+        /*
+         * Receives are limited per user
+         */
+
+        let receive = |deps: DepsMut, env: Env, sender: &str, token_id: &str| {
+            try_receive_nft(
+                deps,
+                env,
+                mock_info(&terra_coll_addr, &[]),
+                sender.into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+        };
+        receive(deps.as_mut(), env.to_owned(), "terra alice", "1").unwrap();
+        receive(deps.as_mut(), env.to_owned(), "terra alice", "2").unwrap();
+        let err = receive(deps.as_mut(), env.to_owned(), "terra alice", "3").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Rate limit for user \"terra alice\" exceeded"
+        );
+        receive(deps.as_mut(), env.to_owned(), "terra bob", "3").unwrap();
+        for token_id in ["1", "2", "3"] {
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+
+        /*
+         * Releases are limited per collection and per operator
+         */
+
+        let release = |deps: DepsMut, env: Env, operator: &str, token_id: &str| {
+            try_release_nft(
+                deps,
+                env,
+                mock_info(operator, &[]),
+                sn_coll_addr.to_owned(),
+                "secret sender".into(),
+                "terra recipient".into(),
+                token_id.into(),
+                Some(false),
+                None,
+                None,
+            )
+        };
+        release(deps.as_mut(), env.to_owned(), "tommy", "1").unwrap();
+        release(deps.as_mut(), env.to_owned(), "titan", "2").unwrap();
+        let err = release(deps.as_mut(), env.to_owned(), "tommy", "3").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Rate limit for collection \"terra contract\" exceeded"
+        );
+
+        // The rejected release did not count against the operator
+        let usage: RateLimitUsageResponse = from_binary(
+            &query_rate_limit_usage(
+                deps.as_ref(),
+                env.to_owned(),
+                Some(terra_coll_addr.to_owned()),
+                Some("terra alice".into()),
+                Some("tommy".into()),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            usage,
+            RateLimitUsageResponse {
+                limits: Some(limits),
+                collection: Some(WindowUsageResponse {
+                    used: 2,
+                    limit: Some(2),
+                }),
+                user: Some(WindowUsageResponse {
+                    used: 2,
+                    limit: Some(2),
+                }),
+                operator: Some(WindowUsageResponse {
+                    used: 1,
+                    limit: Some(2),
+                }),
+            }
+        );
+
+        /*
+         * Usage expires once it leaves the window
+         */
+
+        env.block.time = env.block.time.plus_seconds(3600);
+        release(deps.as_mut(), env, "tommy", "3").unwrap();
+    }
+
+    #[test]
+    fn circuit_breaker() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        /*
+         * Only admins can configure the circuit breaker
+         */
+
+        let circuit_breaker = CircuitBreaker {
+            window: 3600,
+            max_releases_per_window: Some(1),
+            trip_on_unescrowed_release: true,
+            trip_on_conflicting_acknowledgement: true,
+        };
+        let err = try_update_circuit_breaker(
+            deps.as_mut(),
+            info_oper.to_owned(),
+            Some(circuit_breaker.to_owned()),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_update_circuit_breaker(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            Some(circuit_breaker.to_owned()),
+        )
+        .unwrap();
+
+        for token_id in ["1", "2", "3"] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+        }
+        for token_id in ["1", "2", "9"] {
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+        let release = |deps: DepsMut, token_id: &str| {
+            try_release_nft(
+                deps,
+                mock_env(),
+                mock_info("tommy", &[]),
+                sn_coll_addr.to_owned(),
+                "secret sender".into(),
+                "terra recipient".into(),
+                token_id.into(),
+                Some(false),
+                None,
+                None,
+            )
+        };
+        let pause_status = |deps: &OwnedDeps<_, _, _>| -> PauseStatusResponse {
+            from_binary(
+                &query_pause_status(deps.as_ref(), Some(terra_coll_addr.to_owned())).unwrap(),
+            )
+            .unwrap()
+        };
+
+        /*
+         * Exceeding the release threshold pauses the collection
+         */
+
+        release(deps.as_mut(), "1").unwrap();
+        let response = release(deps.as_mut(), "2").unwrap();
+        assert!(response.messages.is_empty());
+        assert_eq!(
+            response.events,
+            vec![Event::new("circuit_breaker_tripped")
+                .add_attribute("scope", terra_coll_addr.to_owned())
+                .add_attribute("reason", "release_threshold")]
+        );
+        let attribute = |key: &str| {
+            response
+                .attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.to_owned())
+        };
+        assert_eq!(attribute("action"), Some("release_tripped".into()));
+        assert_eq!(attribute("scope"), Some(terra_coll_addr.to_owned()));
+        assert_eq!(attribute("reason"), Some("release_threshold".into()));
+        assert_eq!(attribute("history_chain_seq"), Some("5".into()));
+        assert_eq!(
+            pause_status(&deps),
+            PauseStatusResponse {
+                is_paused: false,
+                trip: None,
+                is_collection_paused: Some(true),
+                collection_trip: Some(Trip {
+                    reason: TripReason::ReleaseThreshold {
+                        max: 1,
+                        window: 3600,
+                    },
+                    tripped_at: env.block.time.seconds(),
+                }),
+                circuit_breaker: Some(circuit_breaker.to_owned()),
+            }
+        );
+        let err = release(deps.as_mut(), "2").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge is in the paused state. Tokens cannot be transfered in or out."
+        );
+
+        // Unpausing clears the trip
+        try_update_pause(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            false,
+            Some(terra_coll_addr.to_owned()),
+        )
+        .unwrap();
+        let status = pause_status(&deps);
+        assert_eq!(status.is_collection_paused, Some(false));
+        assert_eq!(status.collection_trip, None);
+
+        /*
+         * Releasing a token missing from the escrow ledger pauses the collection
+         */
+
+        let mut unescrowed_only = circuit_breaker.to_owned();
+        unescrowed_only.max_releases_per_window = None;
+        try_update_circuit_breaker(deps.as_mut(), info_admin.to_owned(), Some(unescrowed_only))
+            .unwrap();
+        let response = release(deps.as_mut(), "9").unwrap();
+        assert!(response.messages.is_empty());
+        assert_eq!(
+            pause_status(&deps).collection_trip.unwrap().reason,
+            TripReason::UnescrowedRelease {
+                token_id: "9".into()
+            }
+        );
+        try_update_pause(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            false,
+            Some(terra_coll_addr.to_owned()),
+        )
+        .unwrap();
+
+        // A trip ends a batch and reports the entries it did not process. Releases made
+        // before the trip are still dispatched
+        let request = |token_id: &str| ReleaseRequest {
+            recipient: "terra recipient".into(),
+            sn_collection: sn_coll_addr.to_owned(),
+            sn_address: "secret sender".into(),
+            token_id: token_id.into(),
+            recipient_is_contract: Some(false),
+            msg: None,
+        };
+        let response = try_release_nfts(
+            deps.as_mut(),
+            mock_env(),
+            info_oper.to_owned(),
+            vec![request("2"), request("9"), request("1")],
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(response.messages.len(), 1);
+        assert!(response
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "released" && attr.value == "1"));
+        let trip = ContractError::CircuitBreakerTripped {
+            scope: terra_coll_addr.to_owned(),
+            reason: "unescrowed_release".into(),
+        }
+        .to_string();
+        let data: ReleaseNftsResponse = from_binary(&response.data.unwrap()).unwrap();
+        assert_eq!(
+            data.skipped,
+            vec![
+                SkippedRelease {
+                    index: 1,
+                    token_id: "9".into(),
+                    error: trip.to_owned(),
+                },
+                SkippedRelease {
+                    index: 2,
+                    token_id: "1".into(),
+                    error: trip,
+                },
+            ]
+        );
+        try_update_pause(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            false,
+            Some(terra_coll_addr.to_owned()),
+        )
+        .unwrap();
+
+        // Invalid releases are rejected without tripping the breaker
+        let err = release(deps.as_mut(), "8").unwrap_err();
+        assert_eq!(err.to_string(), "Token \"8\" is not held by the bridge");
+        assert_eq!(pause_status(&deps).is_collection_paused, Some(false));
+
+        /*
+         * A conflicting acknowledgement pauses the whole bridge
+         */
+
+        try_acknowledge_bridge(
+            deps.as_mut(),
+            mock_env(),
+            info_oper.to_owned(),
+            3,
+            Some("sn hash".into()),
+            true,
+        )
+        .unwrap();
+
+        // Repeating the same acknowledgement is not a conflict
+        let err = try_acknowledge_bridge(
+            deps.as_mut(),
+            mock_env(),
+            info_oper.to_owned(),
+            3,
+            Some("sn hash".into()),
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge record 3 is not awaiting acknowledgement"
+        );
+
+        // A missing transaction hash with the same outcome is not a conflict
+        let err = try_acknowledge_bridge(
+            deps.as_mut(),
+            mock_env(),
+            info_oper.to_owned(),
+            3,
+            None,
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge record 3 is not awaiting acknowledgement"
+        );
+        assert!(!pause_status(&deps).is_paused);
+
+        let response =
+            try_acknowledge_bridge(deps.as_mut(), mock_env(), info_oper, 3, None, false).unwrap();
+        assert_eq!(
+            response.events,
+            vec![Event::new("circuit_breaker_tripped")
+                .add_attribute("scope", "bridge")
+                .add_attribute("reason", "conflicting_acknowledgement")]
+        );
+        let status = pause_status(&deps);
+        assert!(status.is_paused);
+        assert_eq!(
+            status.trip.unwrap().reason,
+            TripReason::ConflictingAcknowledgement { seq: 3 }
+        );
+    }
+
+    #[test]
+    fn time_locked_releases() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+        for token_id in ["1", "2"] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+
+        /*
+         * Only admins can time-lock a collection and add guardians
+         */
+
+        let err = try_update_release_delay(
+            deps.as_mut(),
+            info_oper.to_owned(),
+            terra_coll_addr.to_owned(),
+            Some(600),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_update_release_delay(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            terra_coll_addr.to_owned(),
+            Some(600),
+        )
+        .unwrap();
+        let err = try_update_guardians(
+            deps.as_mut(),
+            info_oper.to_owned(),
+            Some(vec!["guardian".into()]),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_update_guardians(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            Some(vec!["guardian".into()]),
+            None,
+        )
+        .unwrap();
+        let guardians: GuardiansResponse =
+            from_binary(&query_guardians(deps.as_ref()).unwrap()).unwrap();
+        assert_eq!(guardians.guardians, vec![Addr::unchecked("guardian")]);
+
+        /*
+         * Releases from a time-locked collection are queued
+         */
+
+        let release = |deps: DepsMut, token_id: &str| {
+            try_release_nft(
+                deps,
+                mock_env(),
+                mock_info("tommy", &[]),
+                sn_coll_addr.to_owned(),
+                "secret sender".into(),
+                "terra recipient".into(),
+                token_id.into(),
+                Some(false),
+                None,
+                None,
+            )
+        };
+        let response = release(deps.as_mut(), "1").unwrap();
+        assert!(response.messages.is_empty());
+        assert_eq!(response.attributes[0].value, "queue_release");
+        let unlock_at = env.block.time.seconds() + 600;
+
+        let err = release(deps.as_mut(), "1").unwrap_err();
+        assert_eq!(err.to_string(), "Token \"1\" already has a queued release");
+
+        // Claims would bypass the queue
+        let err = try_mark_claimable(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            sn_coll_addr.to_owned(),
+            "secret sender".into(),
+            "terra recipient".into(),
+            "2".into(),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Releases from collection \"terra contract\" are time-locked and must be queued"
+        );
+
+        let queued: QueuedReleasesResponse =
+            from_binary(&query_queued_releases(deps.as_ref(), None, None).unwrap()).unwrap();
+        assert_eq!(
+            queued.releases,
+            vec![QueuedReleaseResponse {
+                queue_id: 1,
+                collection: terra_coll_addr.to_owned(),
+                token_id: "1".into(),
+                recipient: "terra recipient".into(),
+                sn_collection: sn_coll_addr.to_owned(),
+                sn_address: "secret sender".into(),
+                queued_at: env.block.time.seconds(),
+                unlock_at,
+                challenge: None,
+            }]
+        );
+
+        /*
+         * Guardians and admins can cancel queued releases
+         */
+
+        release(deps.as_mut(), "2").unwrap();
+        let err = try_cancel_queued_release(deps.as_mut(), info_oper, 2).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_cancel_queued_release(deps.as_mut(), mock_info("guardian", &[]), 2).unwrap();
+        let err = try_execute_queued_release(deps.as_mut(), env.to_owned(), 2).unwrap_err();
+        assert_eq!(err.to_string(), "No queued release found with id 2");
+
+        /*
+         * Anyone can execute a queued release once it unlocks
+         */
+
+        let err = try_execute_queued_release(deps.as_mut(), env.to_owned(), 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Queued release 1 is locked until {}", unlock_at)
+        );
+
+        env.block.time = env.block.time.plus_seconds(600);
+        let response = try_execute_queued_release(deps.as_mut(), env, 1).unwrap();
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: terra_coll_addr,
+                    msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                        recipient: "terra recipient".into(),
+                        token_id: "1".into(),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                },
+                1
+            )]
+        );
+        let queued: QueuedReleasesResponse =
+            from_binary(&query_queued_releases(deps.as_ref(), None, None).unwrap()).unwrap();
+        assert!(queued.releases.is_empty());
+    }
+
+    #[test]
+    fn optimistic_releases() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let info_watcher = mock_info("watcher", &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+        for token_id in ["1", "2"] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+
+        /*
+         * Only admins can set the challenge period and add watchers
+         */
+
+        let err = try_update_challenge_period(deps.as_mut(), info_oper.to_owned(), Some(300))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_update_challenge_period(deps.as_mut(), info_admin.to_owned(), Some(300)).unwrap();
+        try_update_watchers(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            Some(vec!["watcher".into()]),
+            None,
+        )
+        .unwrap();
+        let watchers: WatchersResponse =
+            from_binary(&query_watchers(deps.as_ref()).unwrap()).unwrap();
+        assert_eq!(
+            watchers,
+            WatchersResponse {
+                watchers: vec![Addr::unchecked("watcher")],
+                challenge_period: Some(300),
+            }
+        );
+
+        /*
+         * Every release is queued for the challenge period
+         */
+
+        for token_id in ["1", "2"] {
+            let response = try_release_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                info_oper.to_owned(),
+                sn_coll_addr.to_owned(),
+                "secret sender".into(),
+                "terra recipient".into(),
+                token_id.into(),
+                Some(false),
+                None,
+                None,
+            )
+            .unwrap();
+            assert!(response.messages.is_empty());
+        }
+
+        // The challenge period applies to every collection, and claims are disabled
+        let other_coll_addr = "terra other".to_string();
+        let add_list = vec![CollectionMapping {
+            source: other_coll_addr.to_owned(),
+            destination: "secret other".into(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+        try_receive_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info(&other_coll_addr, &[]),
+            "terra sender".into(),
+            "1".into(),
+            to_binary(&"secret sender").unwrap(),
+        )
+        .unwrap();
+        deps.querier
+            .set_owner(&other_coll_addr, "1", MOCK_CONTRACT_ADDR);
+        let err = try_mark_claimable(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            "secret other".into(),
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Releases from collection \"terra other\" are time-locked and must be queued"
+        );
+        let response = try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            "secret other".into(),
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            Some(false),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(response.messages.is_empty());
+        assert_eq!(response.attributes[0].value, "queue_release");
+
+        /*
+         * Watchers can challenge a queued release, which pauses the collection
+         */
+
+        let err = try_challenge_release(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            1,
+            "stolen".into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_challenge_release(
+            deps.as_mut(),
+            env.to_owned(),
+            info_watcher.to_owned(),
+            1,
+            "stolen".into(),
+        )
+        .unwrap();
+        let err = try_challenge_release(
+            deps.as_mut(),
+            env.to_owned(),
+            info_watcher,
+            1,
+            "stolen".into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Queued release 1 has been challenged");
+
+        let queued: QueuedReleasesResponse =
+            from_binary(&query_queued_releases(deps.as_ref(), None, Some(1)).unwrap()).unwrap();
+        assert_eq!(
+            queued.releases[0].challenge,
+            Some(Challenge {
+                watcher: Addr::unchecked("watcher"),
+                reason: "stolen".into(),
+                challenged_at: env.block.time.seconds(),
+            })
+        );
+        let status: PauseStatusResponse = from_binary(
+            &query_pause_status(deps.as_ref(), Some(terra_coll_addr.to_owned())).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(status.is_collection_paused, Some(true));
+
+        // Neither release can be executed once the challenge period ends
+        env.block.time = env.block.time.plus_seconds(300);
+        let err = try_execute_queued_release(deps.as_mut(), env.to_owned(), 1).unwrap_err();
+        assert_eq!(err.to_string(), "Queued release 1 has been challenged");
+        let err = try_execute_queued_release(deps.as_mut(), env.to_owned(), 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge is in the paused state. Tokens cannot be transfered in or out."
+        );
+
+        // Releases can no longer be challenged once they unlock
+        let err = try_challenge_release(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("watcher", &[]),
+            2,
+            "too late".into(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Queued release 2 unlocked at {} and can no longer be challenged",
+                env.block.time.seconds()
+            )
+        );
+
+        /*
+         * Admins can dismiss a challenge
+         */
+
+        let err = try_dismiss_challenge(deps.as_mut(), info_oper, 1).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_dismiss_challenge(deps.as_mut(), info_admin.to_owned(), 1).unwrap();
+        let err = try_dismiss_challenge(deps.as_mut(), info_admin.to_owned(), 1).unwrap_err();
+        assert_eq!(err.to_string(), "Queued release 1 has not been challenged");
+
+        try_update_pause(deps.as_mut(), info_admin, false, Some(terra_coll_addr)).unwrap();
+        try_execute_queued_release(deps.as_mut(), env.to_owned(), 1).unwrap();
+        try_execute_queued_release(deps.as_mut(), env, 2).unwrap();
+    }
+
+    #[test]
+    fn operator_bonds() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        /*
+         * Only admins can enable bonding
+         */
+
+        let config = BondConfigMsg {
+            denom: "uluna".into(),
+            min_bond: Uint128::new(100),
+            unbonding_period: 600,
+            treasury: "treasury".into(),
+            slash_approvals: 2,
+        };
+        let err =
+            try_update_bond_config(deps.as_mut(), info_oper.to_owned(), Some(config.to_owned()))
+                .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        let mut invalid_config = config.to_owned();
+        invalid_config.treasury = "t".into();
+        let err =
+            try_update_bond_config(deps.as_mut(), info_admin.to_owned(), Some(invalid_config))
+                .unwrap_err();
+        assert!(err.to_string().contains("Invalid input"));
+        try_update_bond_config(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            Some(config.to_owned()),
+        )
+        .unwrap();
+
+        // Operators without a bond are inactive
+        let acknowledge = |deps: DepsMut| {
+            try_acknowledge_bridge(deps, mock_env(), mock_info("tommy", &[]), 1, None, true)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(acknowledge(deps.as_mut()), "Unauthorized");
+
+        // Admins can act as operators without a bond
+        let err = try_acknowledge_bridge(
+            deps.as_mut(),
+            mock_env(),
+            info_admin.to_owned(),
+            1,
+            None,
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge record 1 is not awaiting acknowledgement"
+        );
+
+        /*
+         * Operators bond funds in the configured denom
+         */
+
+        let err = try_bond(deps.as_mut(), mock_info("tommy", &coins(100, "uusd"))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bonds must be paid in a single coin of \"uluna\""
+        );
+        let err = try_bond(deps.as_mut(), mock_info("rando", &coins(100, "uluna"))).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_bond(deps.as_mut(), mock_info("tommy", &coins(100, "uluna"))).unwrap();
+        deps.querier
+            .base
+            .update_balance(MOCK_CONTRACT_ADDR, coins(100, "uluna"));
+        assert_eq!(
+            acknowledge(deps.as_mut()),
+            "Bridge record 1 is not awaiting acknowledgement"
+        );
+
+        // The bond is locked while the address is an operator
+        let err = try_unbond(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            Uint128::new(100),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The bond is locked while the address is an operator"
+        );
+
+        // Admins can not withdraw bonded funds
+        let err = try_withdraw_native(
+            deps.as_mut(),
+            env.to_owned(),
+            info_admin.to_owned(),
+            "terra recipient".into(),
+            coins(1, "uluna"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Withdrawal would spend funds bonded by operators"
+        );
+
+        // Bonding can not be disabled or moved to another denom while funds are bonded
+        let outstanding = "Bonding can not be disabled or change denom while funds are bonded";
+        let err = try_update_bond_config(deps.as_mut(), info_admin.to_owned(), None).unwrap_err();
+        assert_eq!(err.to_string(), outstanding);
+        let mut uusd_config = config.to_owned();
+        uusd_config.denom = "uusd".into();
+        let err = try_update_bond_config(deps.as_mut(), info_admin.to_owned(), Some(uusd_config))
+            .unwrap_err();
+        assert_eq!(err.to_string(), outstanding);
+
+        /*
+         * Slashing needs approvals from enough admins
+         */
+
+        let response = try_propose_slash(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            "tommy".into(),
+            Uint128::new(40),
+            "released token 7 twice".into(),
+        )
+        .unwrap();
+        assert!(response.messages.is_empty());
+        let err =
+            try_approve_slash(deps.as_mut(), info_admin.to_owned(), "tommy".into()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The slash has already been approved by this admin"
+        );
+        let err =
+            try_approve_slash(deps.as_mut(), info_oper.to_owned(), "tommy".into()).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        let response =
+            try_approve_slash(deps.as_mut(), mock_info("champ", &[]), "tommy".into()).unwrap();
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "treasury".into(),
+                amount: coins(40, "uluna"),
+            })]
+        );
+
+        // The slashed operator's bond is below the minimum
+        assert_eq!(acknowledge(deps.as_mut()), "Unauthorized");
+        let bonds: BondsResponse =
+            from_binary(&query_bonds(deps.as_ref(), None, None).unwrap()).unwrap();
+        assert_eq!(
+            bonds,
+            BondsResponse {
+                config: Some(BondConfig {
+                    denom: "uluna".into(),
+                    min_bond: Uint128::new(100),
+                    unbonding_period: 600,
+                    treasury: Addr::unchecked("treasury"),
+                    slash_approvals: 2,
+                }),
+                bonds: vec![BondResponse {
+                    operator: "tommy".into(),
+                    bond: Bond {
+                        amount: Uint128::new(60),
+                        unbonding: Uint128::zero(),
+                        unbonded_at: None,
+                    },
+                    is_active: false,
+                    slash_proposal: None,
+                }],
+            }
+        );
+
+        /*
+         * Former operators can unbond and claim after the unbonding period
+         */
+
+        try_update_super_users(
+            deps.as_mut(),
+            info_admin,
+            false,
+            None,
+            Some(vec!["tommy".into()]),
+        )
+        .unwrap();
+        let err = try_unbond(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            Uint128::new(100),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Only 60 is bonded");
+        try_unbond(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            Uint128::new(60),
+        )
+        .unwrap();
+
+        let err = try_claim_bond(deps.as_mut(), env.to_owned(), info_oper.to_owned()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Nothing to claim until {}", env.block.time.seconds() + 600)
+        );
+        env.block.time = env.block.time.plus_seconds(600);
+        let response = try_claim_bond(deps.as_mut(), env, info_oper).unwrap();
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "tommy".into(),
+                amount: coins(60, "uluna"),
+            })]
+        );
+        let bonds: BondsResponse =
+            from_binary(&query_bonds(deps.as_ref(), None, None).unwrap()).unwrap();
+        assert!(bonds.bonds.is_empty());
+
+        // Bonding can be disabled once every bond is claimed
+        try_update_bond_config(deps.as_mut(), mock_info(CREATOR, &[]), None).unwrap();
+    }
+
+    #[test]
+    fn heartbeats() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: "secret contract".into(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+        let receive = |deps: DepsMut, env: Env, token_id: &str| {
+            try_receive_nft(
+                deps,
+                env,
+                mock_info("terra contract", &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+        };
+
+        /*
+         * Only operators can send heartbeats, and only admins can set the liveness window
+         */
+
+        let err =
+            try_heartbeat(deps.as_mut(), env.to_owned(), mock_info("rando", &[]), 10).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        let err =
+            try_update_liveness_window(deps.as_mut(), info_oper.to_owned(), Some(300)).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        try_update_liveness_window(deps.as_mut(), info_admin, Some(300)).unwrap();
+
+        /*
+         * Tokens are only received while an operator has sent a recent heartbeat
+         */
+
+        let offline =
+            "No operator has sent a heartbeat recently. The bridge is not receiving tokens";
+        let err = receive(deps.as_mut(), env.to_owned(), "1").unwrap_err();
+        assert_eq!(err.to_string(), offline);
+
+        try_heartbeat(deps.as_mut(), env.to_owned(), info_oper, 1000).unwrap();
+        receive(deps.as_mut(), env.to_owned(), "1").unwrap();
+
+        let heartbeats: HeartbeatsResponse =
+            from_binary(&query_heartbeats(deps.as_ref(), env.to_owned(), None, None).unwrap())
+                .unwrap();
+        assert_eq!(
+            heartbeats,
+            HeartbeatsResponse {
+                liveness_window: Some(300),
+                is_live: true,
+                heartbeats: vec![HeartbeatResponse {
+                    operator: "tommy".into(),
+                    heartbeat: Heartbeat {
+                        last_sn_height: 1000,
+                        block_height: env.block.height,
+                        block_time: env.block.time.seconds(),
+                    },
+                }],
+            }
+        );
+
+        // The bridge stops receiving tokens once the heartbeat is stale
+        env.block.time = env.block.time.plus_seconds(300);
+        let err = receive(deps.as_mut(), env.to_owned(), "2").unwrap_err();
+        assert_eq!(err.to_string(), offline);
+        let heartbeats: HeartbeatsResponse =
+            from_binary(&query_heartbeats(deps.as_ref(), env.to_owned(), None, None).unwrap())
+                .unwrap();
+        assert!(!heartbeats.is_live);
+
+        // Any operator's heartbeat revives it
+        try_heartbeat(deps.as_mut(), env.to_owned(), mock_info("titan", &[]), 1050).unwrap();
+        receive(deps.as_mut(), env, "2").unwrap();
+    }
+
+    #[test]
+    fn relayer_checkpoints() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+        for token_id in ["1", "2"] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                mock_info(&terra_coll_addr, &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+            deps.querier
+                .set_owner(&terra_coll_addr, token_id, MOCK_CONTRACT_ADDR);
+        }
+        let checkpoint = |sn_height: u64, event_index: u32| Checkpoint {
+            sn_height,
+            event_index,
+        };
+        let query = |deps: Deps, operator: Option<&str>| -> CheckpointResponse {
+            from_binary(&query_checkpoint(deps, operator.map(String::from)).unwrap()).unwrap()
+        };
+
+        /*
+         * Only operators can checkpoint
+         */
+
+        let err =
+            try_checkpoint(deps.as_mut(), mock_info("rando", &[]), checkpoint(1, 0)).unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+        assert_eq!(
+            query(deps.as_ref(), None),
+            CheckpointResponse {
+                global: None,
+                operator: None,
+            }
+        );
+
+        /*
+         * Releases advance the sender's checkpoint
+         */
+
+        let response = try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            sn_coll_addr.to_owned(),
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            Some(false),
+            None,
+            Some(checkpoint(100, 2)),
+        )
+        .unwrap();
+        assert!(response
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "sn_height" && attr.value == "100"));
+        assert_eq!(
+            query(deps.as_ref(), Some("tommy")),
+            CheckpointResponse {
+                global: Some(checkpoint(100, 2)),
+                operator: Some(checkpoint(100, 2)),
+            }
+        );
+
+        /*
+         * Checkpoints can not move back, and a failed release leaves them untouched
+         */
+
+        let err =
+            try_checkpoint(deps.as_mut(), mock_info("tommy", &[]), checkpoint(100, 1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Checkpoint can not move back from SN height 100 event 2"
+        );
+        try_checkpoint(deps.as_mut(), mock_info("tommy", &[]), checkpoint(100, 2)).unwrap();
+
+        // The SN collection is not mapped
+        try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            "unknown contract".into(),
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            Some(false),
+            None,
+            Some(checkpoint(150, 0)),
+        )
+        .unwrap_err();
+        assert_eq!(
+            query(deps.as_ref(), Some("tommy")).operator,
+            Some(checkpoint(100, 2))
+        );
+
+        // Skipped releases must be retried, so they also leave the checkpoint untouched
+        let skipped = vec![ReleaseRequest {
+            recipient: "terra recipient".into(),
+            sn_collection: sn_coll_addr.to_owned(),
+            sn_address: "secret sender".into(),
+            token_id: "3".into(),
+            recipient_is_contract: Some(false),
+            msg: None,
+        }];
+        let response = try_release_nfts(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            skipped,
+            Some(true),
+            Some(checkpoint(150, 0)),
+        )
+        .unwrap();
+        let data: ReleaseNftsResponse = from_binary(&response.data.unwrap()).unwrap();
+        assert_eq!(data.skipped.len(), 1);
+        assert_eq!(
+            query(deps.as_ref(), Some("tommy")).operator,
+            Some(checkpoint(100, 2))
+        );
+
+        /*
+         * The global checkpoint only tracks the furthest operator
+         */
+
+        try_checkpoint(deps.as_mut(), mock_info("titan", &[]), checkpoint(90, 7)).unwrap();
+        assert_eq!(
+            query(deps.as_ref(), Some("titan")),
+            CheckpointResponse {
+                global: Some(checkpoint(100, 2)),
+                operator: Some(checkpoint(90, 7)),
+            }
+        );
+        let releases = vec![ReleaseRequest {
+            recipient: "terra recipient".into(),
+            sn_collection: sn_coll_addr,
+            sn_address: "secret sender".into(),
+            token_id: "2".into(),
+            recipient_is_contract: Some(false),
+            msg: None,
+        }];
+        try_release_nfts(
+            deps.as_mut(),
+            env,
+            mock_info("titan", &[]),
+            releases,
+            None,
+            Some(checkpoint(101, 0)),
+        )
+        .unwrap();
+        assert_eq!(
+            query(deps.as_ref(), Some("tommy")),
+            CheckpointResponse {
+                global: Some(checkpoint(101, 0)),
+                operator: Some(checkpoint(100, 2)),
+            }
+        );
+    }
+
+    #[test]
+    fn history_chain() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_oper = mock_info("tommy", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = Addr::unchecked("terra contract");
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_string(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin, None, Some(add_list)).unwrap();
+        let query = |deps: Deps| -> HistoryChainResponse {
+            from_binary(&query_history_chain(deps).unwrap()).unwrap()
+        };
+        assert_eq!(
+            query(deps.as_ref()),
+            HistoryChainResponse { seq: 0, head: None }
+        );
+
+        // Replays the chain from the stored links, checking each link's head
+        let mut head: Vec<u8> = vec![];
+        let mut link = |deps: Deps, seq: u64, history_id: u64| -> Binary {
+            let links: HistoryLinksResponse =
+                from_binary(&query_history_links(deps, Some(seq - 1), Some(1)).unwrap()).unwrap();
+            let link = links.links[0].to_owned();
+            assert_eq!((link.seq, link.history_id), (seq, history_id));
+            let mut preimage = head.to_owned();
+            preimage.extend(
+                to_vec(&HistoryLink {
+                    collection: &Addr::unchecked(link.collection),
+                    token_id: &link.token_id,
+                    history_id: link.history_id,
+                    record: &link.record,
+                })
+                .unwrap(),
+            );
+            head = Sha256::digest(&preimage).to_vec();
+            assert_eq!(link.head.as_slice(), head.as_slice());
+            link.head
+        };
+        let chain_attributes = |response: &Response| -> Vec<String> {
+            response
+                .attributes
+                .iter()
+                .filter(|attr| attr.key.starts_with("history_chain"))
+                .map(|attr| attr.value.to_owned())
+                .collect()
+        };
+
+        /*
+         * Receiving a token chains its record
+         */
+
+        let response = try_receive_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info(terra_coll_addr.as_str(), &[]),
+            "terra sender".into(),
+            "1".into(),
+            to_binary(&"secret sender").unwrap(),
+        )
+        .unwrap();
+        let head_1 = link(deps.as_ref(), 1, 1);
+        assert_eq!(
+            chain_attributes(&response),
+            vec!["1".to_string(), head_1.to_base64()]
+        );
+        assert_eq!(
+            query(deps.as_ref()),
+            HistoryChainResponse {
+                seq: 1,
+                head: Some(head_1),
+            }
+        );
+
+        /*
+         * Status changes are chained as well as new records
+         */
+
+        let response = try_acknowledge_bridge(
+            deps.as_mut(),
+            env.to_owned(),
+            info_oper.to_owned(),
+            1,
+            None,
+            true,
+        )
+        .unwrap();
+        let head_2 = link(deps.as_ref(), 2, 1);
+        assert_eq!(
+            chain_attributes(&response),
+            vec!["2".to_string(), head_2.to_base64()]
+        );
+
+        deps.querier
+            .set_owner(terra_coll_addr.as_str(), "1", MOCK_CONTRACT_ADDR);
+        let response = try_release_nft(
+            deps.as_mut(),
+            env,
+            info_oper,
+            sn_coll_addr,
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            Some(false),
+            None,
+            None,
+        )
+        .unwrap();
+        let head_3 = link(deps.as_ref(), 3, 2);
+        assert_eq!(
+            chain_attributes(&response),
+            vec!["3".to_string(), head_3.to_base64()]
+        );
+        assert_eq!(
+            query(deps.as_ref()),
+            HistoryChainResponse {
+                seq: 3,
+                head: Some(head_3),
+            }
+        );
+
+        // The first link keeps the record as it was received, before the acknowledgement
+        let links: HistoryLinksResponse =
+            from_binary(&query_history_links(deps.as_ref(), None, None).unwrap()).unwrap();
+        assert_eq!(links.links.len(), 3);
+        assert_eq!(links.links[0].record.status, Some(BridgeStatus::Pending));
+        assert_eq!(links.links[1].record.status, Some(BridgeStatus::Completed));
+    }
+
+    #[test]
+    fn backfill_escrow() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = Addr::unchecked("terra contract");
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_string(),
+            destination: "secret contract".into(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+
+        // Tokens "1" and "2" were received before the escrow ledger, "2" has since left the bridge
+        for token_id in ["1", "2", "3"] {
+            try_receive_nft(
+                deps.as_mut(),
+                env.to_owned(),
+                mock_info(terra_coll_addr.as_str(), &[]),
+                "terra sender".into(),
+                token_id.into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+            deps.querier
+                .set_owner(terra_coll_addr.as_str(), token_id, MOCK_CONTRACT_ADDR);
+        }
+        for token_id in ["1", "2"] {
+            remove_escrow(
+                &mut deps.storage,
+                terra_coll_addr.to_owned(),
+                token_id.into(),
+            )
+            .unwrap();
+        }
+        deps.querier
+            .set_owner(terra_coll_addr.as_str(), "2", "terra wallet");
+        let escrowed = |deps: Deps, token_id: &str| {
+            ESCROW.has(deps.storage, (terra_coll_addr.to_owned(), token_id.into()))
+        };
+
+        /*
+         * Bridged tokens missing from the ledger can not be rescued
+         */
+
+        let err = try_rescue_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_admin.to_owned(),
+            terra_coll_addr.to_string(),
+            "1".into(),
+            "terra wallet".into(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Token \"1\" is in the escrow ledger and can not be rescued"
+        );
+
+        /*
+         * Only admins can backfill, which restores tokens the bridge still holds
+         */
+
+        let err = try_backfill_escrow(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            terra_coll_addr.to_string(),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+
+        let response = try_backfill_escrow(
+            deps.as_mut(),
+            env.to_owned(),
+            info_admin.to_owned(),
+            terra_coll_addr.to_string(),
+            None,
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(response.attributes[2], attr("checked", "2"));
+        assert_eq!(response.attributes[3], attr("restored", "1"));
+        assert_eq!(response.attributes[4], attr("last_token_id", "2"));
+        assert!(escrowed(deps.as_ref(), "1"));
+        assert!(!escrowed(deps.as_ref(), "2"));
+
+        // The next page finds token "3" already in the ledger
+        let response = try_backfill_escrow(
+            deps.as_mut(),
+            env,
+            info_admin,
+            terra_coll_addr.to_string(),
+            Some("2".into()),
+            None,
+        )
+        .unwrap();
+        assert_eq!(response.attributes[2], attr("checked", "1"));
+        assert_eq!(response.attributes[3], attr("restored", "0"));
+    }
+
+    #[test]
+    fn reclaim_nft_after_release() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_alice = mock_info("terra alice", &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = Addr::unchecked("terra contract");
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_string(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+        try_update_reclaim_timeout(deps.as_mut(), info_admin, 3600).unwrap();
+        let deposit = |deps: DepsMut, depositor: &str| {
+            try_receive_nft(
+                deps,
+                env.to_owned(),
+                mock_info(terra_coll_addr.as_str(), &[]),
+                depositor.into(),
+                "1".into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+            .unwrap();
+        };
+
+        /*
+         * Releasing a token closes its unacknowledged deposit
+         */
+
+        deposit(deps.as_mut(), "terra alice");
+        deps.querier
+            .set_owner(terra_coll_addr.as_str(), "1", MOCK_CONTRACT_ADDR);
+        try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            sn_coll_addr,
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            Some(false),
+            None,
+            None,
+        )
+        .unwrap();
+        let record = HISTORY
+            .load(
+                &deps.storage,
+                (terra_coll_addr.to_owned(), "1".into(), 1.into()),
+            )
+            .unwrap();
+        assert_eq!(record.status, Some(BridgeStatus::Completed));
+
+        /*
+         * The earlier depositor can not reclaim the token once someone else deposits it
+         */
+
+        deposit(deps.as_mut(), "terra bob");
+        env.block.time = env.block.time.plus_seconds(3600);
+        let err =
+            try_reclaim_nft(deps.as_mut(), env.to_owned(), info_alice.to_owned(), 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge record 1 is not awaiting acknowledgement"
+        );
+
+        // Even a stale pending entry does not match the token's latest deposit
+        PENDING_BRIDGES
+            .save(
+                &mut deps.storage,
+                1.into(),
+                &(terra_coll_addr.to_owned(), "1".into(), 1),
+            )
+            .unwrap();
+        let err = try_reclaim_nft(deps.as_mut(), env.to_owned(), info_alice, 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bridge record 1 is not awaiting acknowledgement"
+        );
+
+        // The latest depositor can still reclaim it
+        try_reclaim_nft(deps.as_mut(), env, mock_info("terra bob", &[]), 2).unwrap();
+    }
+
+    #[test]
+    fn queued_release_deposit() {
+        // Instantiate contract
+        let mut deps = mock_deps_cw721();
+        let info_admin = mock_info(CREATOR, &[]);
+        let info_alice = mock_info("terra alice", &[]);
+        let mut env = mock_env();
+        do_instantiate(deps.as_mut(), get_admins(), get_opers()).unwrap();
+
+        let terra_coll_addr = "terra contract".to_string();
+        let sn_coll_addr = "secret contract".to_string();
+        let add_list = vec![CollectionMapping {
+            source: terra_coll_addr.to_owned(),
+            destination: sn_coll_addr.to_owned(),
+            allowlist_only: None,
+        }];
+        try_update_collection_mappings(deps.as_mut(), info_admin.to_owned(), None, Some(add_list))
+            .unwrap();
+        try_update_reclaim_timeout(deps.as_mut(), info_admin.to_owned(), 600).unwrap();
+        try_update_release_delay(
+            deps.as_mut(),
+            info_admin.to_owned(),
+            terra_coll_addr.to_owned(),
+            Some(600),
+        )
+        .unwrap();
+        let deposit = |deps: DepsMut, env: Env, depositor: &str| {
+            try_receive_nft(
+                deps,
+                env,
+                mock_info(&terra_coll_addr, &[]),
+                depositor.into(),
+                "1".into(),
+                to_binary(&"secret sender").unwrap(),
+            )
+        };
+        deposit(deps.as_mut(), env.to_owned(), "terra alice").unwrap();
+        deps.querier
+            .set_owner(&terra_coll_addr, "1", MOCK_CONTRACT_ADDR);
+        try_release_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            mock_info("tommy", &[]),
+            sn_coll_addr,
+            "secret sender".into(),
+            "terra recipient".into(),
+            "1".into(),
+            Some(false),
+            None,
+            None,
+        )
+        .unwrap();
+
+        /*
+         * A queued release only executes for the deposit it was queued for
+         */
+
+        // The token leaves custody outside the bridge and is deposited again
+        let mut later = env.to_owned();
+        later.block.time = later.block.time.plus_seconds(1);
+        deposit(deps.as_mut(), later.to_owned(), "terra alice").unwrap();
+        later.block.time = later.block.time.plus_seconds(600);
+        let err = try_execute_queued_release(deps.as_mut(), later, 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Queued release 1 is for a deposit that is no longer escrowed"
+        );
+
+        /*
+         * A queued token can not leave escrow any other way
+         */
+
+        env.block.time = env.block.time.plus_seconds(1200);
+        let err =
+            try_reclaim_nft(deps.as_mut(), env.to_owned(), info_alice.to_owned(), 2).unwrap_err();
+        assert_eq!(err.to_string(), "Token \"1\" already has a queued release");
+
+        try_wind_down(deps.as_mut(), env.to_owned(), info_admin).unwrap();
+        let err = try_withdraw_nft(
+            deps.as_mut(),
+            env.to_owned(),
+            info_alice,
+            terra_coll_addr.to_owned(),
+            "1".into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Token \"1\" already has a queued release");
+    }
 }
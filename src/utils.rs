@@ -1,6 +1,19 @@
-use cosmwasm_std::{Addr, CanonicalAddr, StdResult, Storage};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, to_vec, Addr, Api, Attribute, Binary, CanonicalAddr,
+    ContractResult, Deps, Empty, Env, QuerierWrapper, QueryRequest, StdError, StdResult, Storage,
+    SystemResult, WasmQuery,
+};
+use cw721::{ContractInfoResponse, Cw721QueryMsg, NumTokensResponse, OwnerOfResponse};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use terra_cosmwasm::TerraQuerier;
 
-use crate::state::{ADMINS, IS_COLL_PAUSED, IS_PAUSED, OPERS};
+use crate::msg::{DenylistQueryMsg, IsDeniedResponse};
+use crate::state::{
+    BridgeRecord, CollectionInfo, ReclaimHold, ADMINS, BONDS, BOND_CONFIG, DEFAULT_RECLAIM_TIMEOUT,
+    DENYLIST, DENYLIST_CONTRACT, GUARDIANS, HISTORY_CHAIN, IS_COLL_PAUSED, IS_PAUSED,
+    LAST_HEARTBEAT, LIVENESS_WINDOW, OPERS, RECLAIM_HOLDS, RECLAIM_TIMEOUT, WATCHERS,
+};
 
 pub fn check_is_paused(store: &dyn Storage, coll_addr: Addr) -> StdResult<bool> {
     let is_paused = IS_PAUSED.load(store)?;
@@ -12,15 +25,67 @@ pub fn check_is_paused(store: &dyn Storage, coll_addr: Addr) -> StdResult<bool>
     Ok(true)
 }
 
+/// Pauses or unpauses the collection, or the whole bridge when `coll_addr` is `None`
+pub fn set_paused(store: &mut dyn Storage, coll_addr: Option<&Addr>, pause: bool) -> StdResult<()> {
+    match coll_addr {
+        Some(coll_addr) => IS_COLL_PAUSED.save(store, coll_addr.to_owned(), &pause),
+        None => IS_PAUSED.save(store, &pause),
+    }
+}
+
+/// Returns `false` if a liveness window is set and no operator has sent a heartbeat within it
+pub fn check_is_live(store: &dyn Storage, env: &Env) -> StdResult<bool> {
+    let window = match LIVENESS_WINDOW.may_load(store)? {
+        Some(window) => window,
+        None => return Ok(true),
+    };
+    Ok(match LAST_HEARTBEAT.may_load(store)? {
+        Some(last_heartbeat) => env.block.time.seconds() < last_heartbeat + window,
+        None => false,
+    })
+}
+
+/// Returns `true` if the sender is an operator whose bond meets the minimum, or an admin.
+/// Admins are trusted with every role, so they never need a bond
 pub fn check_is_operator(store: &dyn Storage, sender_raw: CanonicalAddr) -> StdResult<bool> {
     let opers = OPERS.load(store)?;
-    if !opers.contains(&sender_raw) {
+    if !opers.contains(&sender_raw) || !check_is_bonded(store, &sender_raw)? {
         // Allow admins to update too
         return check_is_admin(store, sender_raw);
     }
     Ok(true)
 }
 
+/// Returns `true` if operators do not need a bond, or the operator's bond meets the minimum
+pub fn check_is_bonded(store: &dyn Storage, sender_raw: &CanonicalAddr) -> StdResult<bool> {
+    let config = match BOND_CONFIG.may_load(store)? {
+        Some(config) => config,
+        None => return Ok(true),
+    };
+    let bond = BONDS
+        .may_load(store, sender_raw.to_vec())?
+        .unwrap_or_default();
+    Ok(bond.amount >= config.min_bond)
+}
+
+pub fn check_is_guardian(store: &dyn Storage, sender_raw: CanonicalAddr) -> StdResult<bool> {
+    let guardians = GUARDIANS.may_load(store)?.unwrap_or_default();
+    if !guardians.contains(&sender_raw) {
+        // Allow admins to cancel too
+        return check_is_admin(store, sender_raw);
+    }
+    Ok(true)
+}
+
+pub fn check_is_watcher(store: &dyn Storage, sender_raw: CanonicalAddr) -> StdResult<bool> {
+    let watchers = WATCHERS.may_load(store)?.unwrap_or_default();
+    if !watchers.contains(&sender_raw) {
+        // Allow admins to challenge too
+        return check_is_admin(store, sender_raw);
+    }
+    Ok(true)
+}
+
 pub fn check_is_admin(store: &dyn Storage, sender_raw: CanonicalAddr) -> StdResult<bool> {
     let admins = ADMINS.load(store)?;
     if !admins.contains(&sender_raw) {
@@ -28,3 +93,150 @@ pub fn check_is_admin(store: &dyn Storage, sender_raw: CanonicalAddr) -> StdResu
     }
     Ok(true)
 }
+
+pub fn query_owner_of(
+    querier: &QuerierWrapper,
+    coll_addr: Addr,
+    token_id: String,
+) -> StdResult<String> {
+    let resp: OwnerOfResponse = querier.query_wasm_smart(
+        coll_addr,
+        &Cw721QueryMsg::OwnerOf {
+            token_id,
+            include_expired: None,
+        },
+    )?;
+    Ok(resp.owner)
+}
+
+/// Returns `true` if `addr` is a smart contract, determined by querying its contract info.
+/// Only a "not found" answer means `addr` is a wallet, any other query error is returned
+pub fn check_is_contract(querier: &QuerierWrapper, addr: &Addr) -> StdResult<bool> {
+    match TerraQuerier::new(querier).query_contract_info(addr.as_str()) {
+        Ok(_) => Ok(true),
+        Err(err) => {
+            let msg = err.to_string().to_lowercase();
+            if msg.contains("not found") || msg.contains("no such contract") {
+                Ok(false)
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Queries the collection's cw721 `ContractInfo` and `NumTokens`.
+/// Fails if `coll_addr` does not answer either query as a cw721 contract.
+pub fn query_collection_info(
+    querier: &QuerierWrapper,
+    coll_addr: Addr,
+) -> StdResult<CollectionInfo> {
+    let info: ContractInfoResponse =
+        querier.query_wasm_smart(coll_addr.to_owned(), &Cw721QueryMsg::ContractInfo {})?;
+    let _: NumTokensResponse = querier.query_wasm_smart(coll_addr, &Cw721QueryMsg::NumTokens {})?;
+    Ok(CollectionInfo {
+        name: info.name,
+        symbol: info.symbol,
+    })
+}
+
+/// The fields of a cw721 `NftInfoResponse` that do not depend on the collection's extension
+#[derive(Deserialize)]
+struct NftInfoUri {
+    token_uri: Option<String>,
+}
+
+/// Queries the collection for a token's metadata.
+/// Returns the raw `NftInfo` response so the extension is kept exactly as the collection sent it,
+/// along with the token's URI.
+pub fn query_nft_info(
+    querier: &QuerierWrapper,
+    coll_addr: Addr,
+    token_id: String,
+) -> StdResult<(Binary, Option<String>)> {
+    let request: QueryRequest<Empty> = WasmQuery::Smart {
+        contract_addr: coll_addr.into_string(),
+        msg: to_binary(&Cw721QueryMsg::NftInfo { token_id })?,
+    }
+    .into();
+    let nft_info = match querier.raw_query(&to_vec(&request)?) {
+        SystemResult::Err(system_err) => {
+            return Err(StdError::generic_err(format!(
+                "Querier system error: {}",
+                system_err
+            )))
+        }
+        SystemResult::Ok(ContractResult::Err(contract_err)) => {
+            return Err(StdError::generic_err(format!(
+                "Querier contract error: {}",
+                contract_err
+            )))
+        }
+        SystemResult::Ok(ContractResult::Ok(value)) => value,
+    };
+    let NftInfoUri { token_uri } = from_binary(&nft_info)?;
+    Ok((nft_info, token_uri))
+}
+
+pub fn sha_256(data: &[u8]) -> Binary {
+    Binary::from(Sha256::digest(data).as_slice())
+}
+
+/// Returns the attributes reporting the history hash chain after this transaction's writes
+pub fn history_chain_attributes(store: &dyn Storage) -> StdResult<Vec<Attribute>> {
+    Ok(match HISTORY_CHAIN.may_load(store)? {
+        Some(chain) => vec![
+            attr("history_chain_seq", chain.seq.to_string()),
+            attr("history_chain_head", chain.head.to_base64()),
+        ],
+        None => vec![],
+    })
+}
+
+/// Returns the time (in seconds since 01/01/1970) from which a pending bridge record can be
+/// reclaimed by its depositor, or `None` if an operator has blocked reclaiming it
+pub fn reclaimable_at(
+    storage: &dyn Storage,
+    seq: u64,
+    record: &BridgeRecord,
+) -> StdResult<Option<u64>> {
+    let timeout = RECLAIM_TIMEOUT
+        .may_load(storage)?
+        .unwrap_or(DEFAULT_RECLAIM_TIMEOUT);
+    let available_at = record.block_time + timeout;
+
+    Ok(match RECLAIM_HOLDS.may_load(storage, seq.into())? {
+        Some(ReclaimHold { until: None }) => None,
+        Some(ReclaimHold { until: Some(until) }) => Some(available_at.max(until)),
+        None => Some(available_at),
+    })
+}
+
+/// Returns the form denylist entries are stored in. Addresses are lowercased, and addresses that
+/// are not SN addresses are validated as Terra addresses
+pub fn normalize_denied_address(api: &dyn Api, address: &str) -> StdResult<String> {
+    let address = address.to_lowercase();
+    if address.starts_with("secret") {
+        return Ok(address);
+    }
+    Ok(api.addr_validate(&address)?.into_string())
+}
+
+/// Returns `true` if `address` is on the bridge's denylist, or on the external denylist contract
+pub fn check_is_denied(deps: Deps, address: &str) -> StdResult<bool> {
+    if DENYLIST.has(deps.storage, address.to_lowercase()) {
+        return Ok(true);
+    }
+    match DENYLIST_CONTRACT.may_load(deps.storage)? {
+        Some(contract) => {
+            let res: IsDeniedResponse = deps.querier.query_wasm_smart(
+                contract,
+                &DenylistQueryMsg::IsDenied {
+                    address: address.to_owned(),
+                },
+            )?;
+            Ok(res.denied)
+        }
+        None => Ok(false),
+    }
+}